@@ -0,0 +1,209 @@
+// The unsigned integers of arbitrary size together with their arithmetic:
+// the Karatsuba and NTT multiplications and Knuth's Algorithm D of "Proof and
+// Implementation of Half-GCD Integer Inversion.rs", and the group orders with
+// the GLV eigenvalues, which the files working with such integers share. They
+// include this one into a module:
+//   #[allow(dead_code)]
+//   mod big { include!("Big.rs"); }
+//   use big::*;
+use std::cmp::Ordering;
+
+// The Goldilocks field arithmetic and the NTT-based convolution
+#[allow(dead_code)]
+mod goldilocks {
+    include!("Goldilocks.rs");
+}
+
+// The order of the groups G1 and G2 of BN254 and its non-trivial cube root of
+// unity, the eigenvalue of the GLV endomorphism of G1
+pub const BN254_R: &str = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+pub const BN254_LAMBDA: &str = "b3c4d79d41a917585bfc41088d8daaa78b17ea66b99c90dd";
+// The order of the group of secp256k1 and its GLV eigenvalue
+pub const SECP256K1_N: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+pub const SECP256K1_LAMBDA: &str = "5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72";
+
+// An unsigned integer of arbitrary size stored as 64-bit limbs, the least
+// significant limb first, without leading zero limbs (0 is the empty vector)
+pub type Big = Vec<u64>;
+
+// Operands shorter than this (in limbs) are multiplied by the schoolbook method
+pub const KARATSUBA_THRESHOLD: usize = 32;
+// Operands at least this long (in limbs) are multiplied by the NTT
+pub const NTT_THRESHOLD: usize = 4096;
+
+pub fn norm(mut a: Big) -> Big {
+    while a.last() == Some(&0) { a.pop(); }
+    a
+}
+
+pub fn from_hex(s: &str) -> Big {
+    let digits: Vec<u64> = s.bytes().rev().map(|c| (c as char).to_digit(16).unwrap() as u64).collect();
+    norm(digits.chunks(16).map(|c| c.iter().rev().fold(0, |r, &d| r << 4 | d)).collect())
+}
+
+pub fn bits(a: &Big) -> usize {
+    a.last().map_or(0, |&top| 64 * a.len() - top.leading_zeros() as usize)
+}
+
+pub fn cmp(a: &Big, b: &Big) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+// Adds x * 2^(64 * offset) to r in place; r must be long enough for the sum
+pub fn add_into(r: &mut Big, x: &[u64], offset: usize) {
+    let mut carry = false;
+    for (i, r) in r[offset..].iter_mut().enumerate() {
+        if i >= x.len() && !carry { break; }
+        let (s1, c1) = r.overflowing_add(*x.get(i).unwrap_or(&0));
+        let (s2, c2) = s1.overflowing_add(carry as u64);
+        (*r, carry) = (s2, c1 | c2);
+    }
+}
+
+pub fn add(a: &Big, b: &Big) -> Big {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut r = a.clone();
+    r.push(0);
+    add_into(&mut r, b, 0);
+    norm(r)
+}
+
+// Returns a - b for a >= b
+pub fn sub(a: &Big, b: &Big) -> Big {
+    let (mut r, mut borrow) = (a.clone(), false);
+    for (i, r) in r.iter_mut().enumerate() {
+        if i >= b.len() && !borrow { break; }
+        let (d1, b1) = r.overflowing_sub(*b.get(i).unwrap_or(&0));
+        let (d2, b2) = d1.overflowing_sub(borrow as u64);
+        (*r, borrow) = (d2, b1 | b2);
+    }
+    assert!(!borrow, "The subtrahend is greater than the minuend!");
+    norm(r)
+}
+
+// Returns a / 2^k
+pub fn shr(a: &Big, k: usize) -> Big {
+    let (limbs, k) = (k / 64, k % 64);
+    if limbs >= a.len() { return vec![]; }
+    let a = &a[limbs..];
+    norm((0..a.len()).map(|i| {
+        let hi = if k > 0 && i + 1 < a.len() { a[i + 1] << (64 - k) } else { 0 };
+        a[i] >> k | hi
+    }).collect())
+}
+
+// Returns a * 2^k for k < 64
+pub fn shl(a: &Big, k: usize) -> Big {
+    if k == 0 { return a.clone(); }
+    let mut r: Big = (0..a.len()).map(|i| a[i] << k | if i > 0 { a[i - 1] >> (64 - k) } else { 0 }).collect();
+    r.push(a.last().map_or(0, |&top| top >> (64 - k)));
+    norm(r)
+}
+
+pub fn mul(a: &Big, b: &Big) -> Big {
+    if a.is_empty() || b.is_empty() { return vec![]; }
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        let mut r = vec![0; a.len() + b.len()];
+        for (i, &x) in a.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &y) in b.iter().enumerate() {
+                let t = r[i + j] as u128 + x as u128 * y as u128 + carry;
+                (r[i + j], carry) = (t as u64, t >> 64);
+            }
+            r[i + b.len()] = carry as u64;
+        }
+        return norm(r);
+    }
+    if a.len().min(b.len()) >= NTT_THRESHOLD { return mul_ntt(a, b); }
+    // Karatsuba: with a = a_0 + a_1 * B and b = b_0 + b_1 * B, where
+    // B = 2^(64h), a * b = z_0 + z_1 * B + z_2 * B^2 for z_0 = a_0 * b_0,
+    // z_2 = a_1 * b_1 and z_1 = (a_0 + a_1) * (b_0 + b_1) - z_0 - z_2
+    let h = a.len().max(b.len()) / 2;
+    let split = |x: &Big| (norm(x[..h.min(x.len())].to_vec()), x[h.min(x.len())..].to_vec());
+    let ((a_0, a_1), (b_0, b_1)) = (split(a), split(b));
+    let (z_0, z_2) = (mul(&a_0, &b_0), mul(&a_1, &b_1));
+    let z_1 = sub(&sub(&mul(&add(&a_0, &a_1), &add(&b_0, &b_1)), &z_0), &z_2);
+    let mut r = vec![0; a.len() + b.len() + 1];
+    add_into(&mut r, &z_0, 0);
+    add_into(&mut r, &z_1, h);
+    add_into(&mut r, &z_2, 2 * h);
+    norm(r)
+}
+
+// Returns a * b for non-empty a and b by the NTT over the Goldilocks field,
+// which costs O(n log n) for n limbs. The numbers are split into 16-bit
+// pieces, the coefficients of the polynomials A and B with A(2^16) = a and
+// B(2^16) = b. Each coefficient of A * B is a sum of at most 4n products
+// below 2^32, so it is below 4n * 2^32 < p for n < 2^29, and the
+// convolution modulo p is exact. Then a * b = (A * B)(2^16) is obtained by
+// propagating the carries; the sizes are limited to 2^28 limbs by the NTT
+pub fn mul_ntt(a: &Big, b: &Big) -> Big {
+    let pieces = |x: &Big| -> Vec<u64> { x.iter().flat_map(|&l| (0..4).map(move |i| l >> (16 * i) & 0xffff)).collect() };
+    let c = goldilocks::convolve(&pieces(a), &pieces(b));
+    let mut r = vec![0; a.len() + b.len()];
+    let mut carry = 0u128;
+    for (i, limb) in r.iter_mut().enumerate() {
+        // Four coefficients below 2^64 with the weights 2^0, 2^16, 2^32 and
+        // 2^48 sum to less than 2^113, so the carry is below 2^49, and t fits
+        // in u128
+        let mut t = carry;
+        for j in 0..4 { t += (*c.get(4 * i + j).unwrap_or(&0) as u128) << (16 * j); }
+        (*limb, carry) = (t as u64, t >> 64);
+    }
+    norm(r)
+}
+
+// Returns the quotient and the remainder of a / b for b > 0 by Knuth's
+// Algorithm D (TAOCP vol. 2, 4.3.1)
+pub fn divrem(a: &Big, b: &Big) -> (Big, Big) {
+    assert!(!b.is_empty(), "Division by zero!");
+    if cmp(a, b) == Ordering::Less { return (vec![], a.clone()); }
+    if b.len() == 1 {
+        let (d, mut rem, mut q) = (b[0] as u128, 0u128, vec![0; a.len()]);
+        for i in (0..a.len()).rev() {
+            let cur = rem << 64 | a[i] as u128;
+            (q[i], rem) = ((cur / d) as u64, cur % d);
+        }
+        return (norm(q), norm(vec![rem as u64]));
+    }
+    // Normalizing b so that its top bit is set makes the estimate of each
+    // quotient limb from the two leading limbs at most 2 too large
+    let shift = b.last().unwrap().leading_zeros() as usize;
+    let (v, mut u) = (shl(b, shift), shl(a, shift));
+    u.resize(a.len() + 1, 0);
+    let n = v.len();
+    let mut q = vec![0; u.len() - n];
+    for j in (0..u.len() - n).rev() {
+        let num = (u[j + n] as u128) << 64 | u[j + n - 1] as u128;
+        let (mut q_hat, mut r_hat) = (num / v[n - 1] as u128, num % v[n - 1] as u128);
+        while q_hat >> 64 != 0 || q_hat * v[n - 2] as u128 > (r_hat << 64 | u[j + n - 2] as u128) {
+            q_hat -= 1;
+            r_hat += v[n - 1] as u128;
+            if r_hat >> 64 != 0 { break; }
+        }
+        // u[j..j + n + 1] -= q_hat * v; if the result is negative, q_hat was
+        // still 1 too large, and v is added back
+        let (mut borrow, mut carry) = (0i128, 0u128);
+        for i in 0..n {
+            let p = q_hat * v[i] as u128 + carry;
+            carry = p >> 64;
+            let t = u[i + j] as i128 - (p as u64) as i128 + borrow;
+            (u[i + j], borrow) = (t as u64, t >> 64);
+        }
+        let t = u[j + n] as i128 - carry as i128 + borrow;
+        u[j + n] = t as u64;
+        if t < 0 {
+            q_hat -= 1;
+            let mut carry = false;
+            for i in 0..n {
+                let (s1, c1) = u[i + j].overflowing_add(v[i]);
+                let (s2, c2) = s1.overflowing_add(carry as u64);
+                (u[i + j], carry) = (s2, c1 | c2);
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u64);
+        }
+        q[j] = q_hat as u64;
+    }
+    u.truncate(n);
+    (norm(q), shr(&norm(u), shift))
+}
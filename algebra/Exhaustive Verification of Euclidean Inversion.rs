@@ -0,0 +1,162 @@
+/*
+   The Exhaustive Verification of the Binary and the Classic Modular Inversion
+                  for All Small Odd Moduli: Implementation in Rust
+
+                               October 2026
+*/
+// The proofs in "Proof and Implementation of Binary Euclidean Inversion.rs"
+// and "Proof and Implementation of Euclidean Inversion.rs" are checked here
+// mechanically over a complete small domain: for every odd n < 2^bits and
+// every x in [1, n) both mod_inv implementations are run, and their results,
+// the claims of the proofs and the agreement with each other are asserted.
+// The files are included verbatim, so the very code of the proofs is tested,
+// the numbers of iterations are counted by the loops of both files
+// themselves, and compiling this file with --cfg 'feature="proof"' also
+// checks the loop invariants of both files in every iteration. Compile with
+// -O. The number of pairs grows 4 times per bit. The default is 16 bits (1.07
+// billion pairs, about 9 minutes on one core), which is the audit setting:
+// the proofs count as checked only after a run with it passes. --quick checks
+// 12 bits (4.2 million pairs, about 2 seconds) for development, and --bits b
+// sets any other b up to 20; 14 bits take about 30 seconds. The moduli are
+// split between all the available cores
+use std::thread;
+
+#[allow(dead_code)]
+mod binary {
+    include!("Proof and Implementation of Binary Euclidean Inversion.rs");
+
+    // Returns the inverse of x modulo n or GCD(x, n) > 1
+    pub fn inv(x: i64, n: i64) -> Result<i64, i64> {
+        match mod_inv(x, n) {
+            Ok(v) => Ok(v),
+            Err(InversionError::NotCoprime { gcd }) => Err(gcd),
+            Err(e) => panic!("Unexpected error {:?} for x = {}, n = {}!", e, x, n),
+        }
+    }
+
+    // The number of iterations of the loop of mod_inv_unchecked for x and n
+    pub fn iterations(x: i64, n: i64) -> u32 {
+        binary_loop(x, n, 1).2
+    }
+}
+
+#[allow(dead_code)]
+mod classic {
+    include!("Proof and Implementation of Euclidean Inversion.rs");
+
+    // Returns the inverse of x modulo n or GCD(x, n) > 1
+    pub fn inv(x: i64, n: i64) -> Result<i64, i64> {
+        match mod_inv(x, n) {
+            Ok(r) => Ok(r),
+            Err(InversionError::NotCoprime { gcd }) => Err(gcd),
+            Err(e) => panic!("Unexpected error {:?} for x = {}, n = {}!", e, x, n),
+        }
+    }
+
+    // The number of iterations of the loop of mod_inv_unchecked for x and n
+    pub fn iterations(x: i64, n: i64) -> u32 {
+        euclid_loop(x.rem_euclid(n), n).2
+    }
+}
+
+fn bits(x: i64) -> u32 {
+    i64::BITS - x.leading_zeros()
+}
+
+fn gcd(mut p: i64, mut q: i64) -> i64 {
+    while q > 0 { (p, q) = (q, p % q); }
+    p
+}
+
+// The results of checking a set of moduli
+#[derive(Default)]
+struct Summary {
+    pairs: u64,
+    invertible: u64,
+    // The maximum number of iterations, its (x, n) and the bound at it
+    binary_max: (u32, i64, i64, u32),
+    classic_max: (u32, i64, i64, u32),
+}
+
+// Checks all x in [1, n) for the odd moduli n = first, first + step, ...
+// below limit; panics at the first violated claim
+fn check(first: i64, step: usize, limit: i64, fib: &[i64]) -> Summary {
+    let mut summary = Summary::default();
+    for n in (first..limit).step_by(step) {
+        // Euler's totient of n, which is the number of invertible x
+        let (mut phi, mut m, mut p) = (n, n, 3);
+        while p * p <= m {
+            if m % p == 0 { phi -= phi / p; }
+            while m % p == 0 { m /= p; }
+            p += 2;
+        }
+        if m > 1 { phi -= phi / m; }
+        let mut invertible = 0;
+        for x in 1..n {
+            let (v, r) = (binary::inv(x, n), classic::inv(x, n));
+            assert!(v == r, "The implementations disagree for x = {}, n = {}!", x, n);
+            match v {
+                Ok(v) => {
+                    assert!(0 < v && v < n, "The inverse is out of range for x = {}, n = {}!", x, n);
+                    assert!(v * x % n == 1, "Incorrect inverse for x = {}, n = {}!", x, n);
+                    invertible += 1;
+                }
+                Err(g) => assert!(g > 1 && g == gcd(n, x), "Incorrect GCD for x = {}, n = {}!", x, n),
+            }
+            // The bound on the iterations of binary_loop and Lame's bound for
+            // euclid_loop, which are proven in the comments to these loops
+            let (k, bound) = (binary::iterations(x, n), bits(x) + bits(n) - 1);
+            assert!(k <= bound, "Too many binary iterations for x = {}, n = {}!", x, n);
+            if k > summary.binary_max.0 { summary.binary_max = (k, x, n, bound); }
+            let k = classic::iterations(x, n);
+            assert!(x >= fib[k as usize + 1] && n >= fib[k as usize + 2], "Lame's bound is broken for x = {}, n = {}!", x, n);
+            if k > summary.classic_max.0 {
+                let bound = fib.iter().rposition(|&f| f <= n).unwrap() as u32 - 2;
+                summary.classic_max = (k, x, n, bound);
+            }
+        }
+        assert!(invertible == phi, "The number of inverses is not phi({})!", n);
+        summary.pairs += n as u64 - 1;
+        summary.invertible += invertible as u64;
+    }
+    summary
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let bits: u32 = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] => 16,
+        ["--quick"] => 12,
+        ["--bits", b] => b.parse().expect("The number of bits is not a number!"),
+        _ => panic!("Usage: [--quick | --bits <b>]; without arguments 16 bits are checked!"),
+    };
+    assert!((2..=20).contains(&bits), "The moduli are too large or too small for an exhaustive check!");
+    let limit = 1i64 << bits;
+    // The Fibonacci numbers F_0, F_1, ... up to the first one above limit
+    let mut fib = vec![0i64, 1];
+    while fib[fib.len() - 1] <= limit { fib.push(fib[fib.len() - 1] + fib[fib.len() - 2]); }
+    if cfg!(feature = "proof") { println!("Proof mode: the invariants are checked in every iteration"); }
+    let threads = thread::available_parallelism().map_or(1, |t| t.get());
+    let summaries: Vec<Summary> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                let fib = &fib;
+                scope.spawn(move || check(3 + 2 * t as i64, 2 * threads, limit, fib))
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    let mut total = Summary::default();
+    for s in summaries {
+        total.pairs += s.pairs;
+        total.invertible += s.invertible;
+        total.binary_max = total.binary_max.max(s.binary_max);
+        total.classic_max = total.classic_max.max(s.classic_max);
+    }
+    println!("Checked {} pairs (x, n) for odd n < 2^{}, {} of them invertible", total.pairs, bits, total.invertible);
+    if bits < 16 { println!("This is below the audit setting of 16 bits!"); }
+    let (k, x, n, bound) = total.binary_max;
+    println!("Binary: at most {} iterations, for x = {}, n = {} (bound {})", k, x, n, bound);
+    let (k, x, n, bound) = total.classic_max;
+    println!("Classic: at most {} iterations, for x = {}, n = {} (Lame's bound {})", k, x, n, bound);
+}
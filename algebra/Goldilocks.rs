@@ -0,0 +1,103 @@
+// The arithmetic of the Goldilocks field, p = 2^64 - 2^32 + 1, which has
+// 2^32-th roots of unity, together with the NTT-based convolution of
+// "Proof and Implementation of Half-GCD Polynomial Inversion.rs". Both the
+// polynomial and the integer multiplication use it; the files include this
+// one into a module:
+//   #[allow(dead_code)]
+//   mod goldilocks { include!("Goldilocks.rs"); }
+//   use goldilocks::*;
+pub const P: u64 = 0xffff_ffff_0000_0001;
+// 2^64 - p = 2^32 - 1
+pub const EPSILON: u64 = 0xffff_ffff;
+// 7 generates the multiplicative group of the field
+pub const GENERATOR: u64 = 7;
+
+pub fn add_mod(a: u64, b: u64) -> u64 {
+    let (s, carry) = a.overflowing_add(b);
+    // If the sum overflows, it is s + 2^64 = s + EPSILON (mod p), and
+    // s + EPSILON < p, since a + b < 2p
+    let s = if carry { s + EPSILON } else { s };
+    if s >= P { s - P } else { s }
+}
+
+pub fn sub_mod(a: u64, b: u64) -> u64 {
+    if a >= b { a - b } else { a + (P - b) }
+}
+
+// Reduces x < 2^128 modulo p using 2^64 = EPSILON and 2^96 = -1 (mod p)
+pub fn reduce(x: u128) -> u64 {
+    let (lo, hi) = (x as u64, (x >> 64) as u64);
+    let (hi_hi, hi_lo) = (hi >> 32, hi & EPSILON);
+    // lo - hi_hi; on a borrow, the wrapped value is 2^64 too large, and
+    // 2^64 = EPSILON (mod p), so we subtract EPSILON, which does not wrap
+    let (t, borrow) = lo.overflowing_sub(hi_hi);
+    let t = if borrow { t - EPSILON } else { t };
+    add_mod(if t >= P { t - P } else { t }, hi_lo * EPSILON % P)
+}
+
+pub fn mul_mod(a: u64, b: u64) -> u64 {
+    reduce(a as u128 * b as u128)
+}
+
+pub fn pow_mod(mut x: u64, mut e: u64) -> u64 {
+    let mut r = 1;
+    while e > 0 {
+        if e & 1 == 1 { r = mul_mod(r, x); }
+        (x, e) = (mul_mod(x, x), e >> 1);
+    }
+    r
+}
+
+// The inverse of a non-zero field element by Fermat's little theorem
+pub fn inv_mod(x: u64) -> u64 {
+    pow_mod(x, P - 2)
+}
+
+// The in-place iterative NTT of size 2^k; omega must be a primitive 2^k-th
+// root of unity
+pub fn ntt(a: &mut [u64], omega: u64) {
+    let n = a.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 { j ^= bit; bit >>= 1; }
+        j |= bit;
+        if i < j { a.swap(i, j); }
+    }
+    let mut len = 2;
+    while len <= n {
+        let w_len = pow_mod(omega, (n / len) as u64);
+        for chunk in a.chunks_mut(len) {
+            let mut w = 1;
+            for i in 0..len / 2 {
+                let (u, v) = (chunk[i], mul_mod(chunk[i + len / 2], w));
+                (chunk[i], chunk[i + len / 2]) = (add_mod(u, v), sub_mod(u, v));
+                w = mul_mod(w, w_len);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+// Returns the a.len() + b.len() - 1 coefficients of the product of the
+// polynomials a and b with coefficients in F_p, a and b non-empty, by the
+// NTT of the smallest power-of-two size n that holds them all. The product
+// modulo X^n - 1 is the ordinary product, since its degree is below n; the
+// size is limited to 2^32
+pub fn convolve(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    assert!(n <= 1 << 32, "The NTT size exceeds 2^32!");
+    let omega = pow_mod(GENERATOR, (P - 1) / n as u64);
+    let (mut fa, mut fb) = (a.to_vec(), b.to_vec());
+    fa.resize(n, 0);
+    fb.resize(n, 0);
+    ntt(&mut fa, omega);
+    ntt(&mut fb, omega);
+    let mut r: Vec<u64> = fa.iter().zip(&fb).map(|(&x, &y)| mul_mod(x, y)).collect();
+    // The inverse transform is the transform with omega^(-1) divided by n
+    ntt(&mut r, inv_mod(omega));
+    let n_inv = inv_mod(n as u64);
+    r.truncate(len);
+    r.iter().map(|&x| mul_mod(x, n_inv)).collect()
+}
@@ -0,0 +1,20 @@
+// The reasons why mod_inv cannot compute the inverse of x modulo n. The enum
+// is shared by the inversion files, which include it by
+// include!("InversionError.rs"); T is the type of the GCD, i64 by default.
+// Each algorithm returns only the variants that apply to it: the classic
+// algorithm accepts any x and any n >= 2, so it never returns EvenModulus,
+// NonPositive or ModulusTooLarge, which are needed for the binary one
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum InversionError<T = i64> {
+    // n < 2, so there is nothing to invert modulo n
+    ModulusTooSmall,
+    // n is even, which the binary algorithm does not support
+    EvenModulus,
+    // x <= 0, which the binary algorithm does not support
+    NonPositive,
+    // n >= 2^62, so u + n may overflow in the loop of the binary algorithm
+    ModulusTooLarge,
+    // GCD(x, n) = gcd > 1, so the inverse does not exist
+    NotCoprime { gcd: T },
+}
@@ -0,0 +1,159 @@
+/*
+   The Iteration Count Analysis of the Binary and the Classic Modular Inversion:
+                            Implementation in Rust
+
+                               October 2026
+*/
+// This tool counts the iterations of the loops of the binary and the classic
+// mod_inv for every odd n in a range and every x in [1, n), reports their
+// distributions and the pairs (x, n) with the most iterations, overall and
+// for each bit length of n, and exports the counts as CSV for the comparison
+// with the bounds: bits(x) + bits(n) - 1 <= 2 * log2(n) + 1 for the binary
+// loop and Lame's bound, the largest k with F_(k + 2) <= n, which is about
+// log(n) / log(phi) = 1.44 * log2(n), for the classic loop; both are proven
+// in the comments to the loops. The files of both algorithms are included
+// verbatim, and the iterations are counted by their loops themselves. Usage:
+//   main [n_min n_max] [--csv file] [--per-input]
+// where the moduli are the odd n with n_min <= n <= n_max (3 and 4097 by
+// default). The CSV file has a row per modulus with the mean and the maximum
+// counts, or, with --per-input, a row per pair (x, n); compile with -O
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+// The number of the worst-case pairs to report for each algorithm
+const WORST: usize = 5;
+
+#[allow(dead_code)]
+mod binary {
+    include!("Proof and Implementation of Binary Euclidean Inversion.rs");
+
+    // The number of iterations of the loop of mod_inv_unchecked for x and n
+    pub fn iterations(x: i64, n: i64) -> u32 {
+        binary_loop(x, n, 1).2
+    }
+}
+
+#[allow(dead_code)]
+mod classic {
+    include!("Proof and Implementation of Euclidean Inversion.rs");
+
+    // The number of iterations of the loop of mod_inv_unchecked for x and n
+    pub fn iterations(x: i64, n: i64) -> u32 {
+        euclid_loop(x.rem_euclid(n), n).2
+    }
+}
+
+// The iteration counts of one algorithm over all the inputs
+struct Stats {
+    name: &'static str,
+    // histogram[k] is the number of inputs with k iterations
+    histogram: Vec<u64>,
+    // worst[l] holds the pairs with the most iterations among the moduli of
+    // bit length l as (k, x, n), the worst first; at equal k the pair found
+    // first, i.e. the one with the smaller n, comes first
+    worst: Vec<Vec<(u32, i64, i64)>>,
+}
+
+impl Stats {
+    fn new(name: &'static str) -> Stats {
+        Stats { name, histogram: vec![], worst: vec![] }
+    }
+
+    fn add(&mut self, k: u32, x: i64, n: i64) {
+        if self.histogram.len() <= k as usize { self.histogram.resize(k as usize + 1, 0); }
+        self.histogram[k as usize] += 1;
+        let l = bits(n) as usize;
+        if self.worst.len() <= l { self.worst.resize(l + 1, vec![]); }
+        let worst = &mut self.worst[l];
+        let pos = worst.iter().position(|w| k > w.0).unwrap_or(worst.len());
+        if pos < WORST {
+            worst.insert(pos, (k, x, n));
+            worst.truncate(WORST);
+        }
+    }
+
+    fn report(&self) {
+        let total: u64 = self.histogram.iter().sum();
+        let mean = self.histogram.iter().enumerate().map(|(k, &c)| k as f64 * c as f64).sum::<f64>() / total as f64;
+        let var = self.histogram.iter().enumerate().map(|(k, &c)| (k as f64 - mean).powi(2) * c as f64).sum::<f64>() / total as f64;
+        println!("{}: {} inputs, mean {:.3} iterations, standard deviation {:.3}", self.name, total, mean, var.sqrt());
+        for (k, &c) in self.histogram.iter().enumerate().filter(|(_, &c)| c > 0) {
+            println!("  {:3} iterations: {:12} ({:.4}%)", k, c, 100.0 * c as f64 / total as f64);
+        }
+        let print = |&(k, x, n): &(u32, i64, i64)| {
+            println!("    x = {}, n = {}: {} iterations, {:.3} * log2(n)", x, n, k, k as f64 / (n as f64).log2());
+        };
+        // The overall worst cases are among the worst ones of the bit
+        // lengths, and the stable sort keeps the smaller n first at equal k
+        let mut overall: Vec<_> = self.worst.iter().flatten().copied().collect();
+        overall.sort_by_key(|w| std::cmp::Reverse(w.0));
+        println!("  the most iterations overall:");
+        overall.iter().take(WORST).for_each(print);
+        println!("  the most iterations for each bit length of n:");
+        for (l, worst) in self.worst.iter().enumerate().filter(|(_, w)| !w.is_empty()) {
+            println!("   {} bits:", l);
+            worst.iter().for_each(print);
+        }
+    }
+}
+
+fn bits(x: i64) -> u32 {
+    i64::BITS - x.leading_zeros()
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut numbers = vec![];
+    let (mut csv, mut per_input) = (None, false);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--csv" => csv = Some(args.next().expect("The CSV file is missing!").clone()),
+            "--per-input" => per_input = true,
+            _ => numbers.push(arg.parse::<i64>().expect("The bound is not a number!")),
+        }
+    }
+    let (n_min, n_max) = match numbers[..] {
+        [] => (3, 4097),
+        [n_min, n_max] => (n_min.max(3), n_max),
+        _ => panic!("The range must be given as n_min n_max!"),
+    };
+    assert!(n_max < 1 << 31, "The moduli are too large for an exhaustive analysis!");
+    // The Fibonacci numbers F_0, F_1, ... up to the first one above n_max
+    let mut fib = vec![0i64, 1];
+    while fib[fib.len() - 1] <= n_max { fib.push(fib[fib.len() - 1] + fib[fib.len() - 2]); }
+    let mut out = csv.map(|path| BufWriter::new(File::create(path).expect("Cannot create the CSV file!")));
+    if let Some(out) = out.as_mut() {
+        let header = if per_input {
+            "x,n,binary,classic"
+        } else {
+            "n,binary_mean,binary_max,binary_worst_x,two_log2_n,classic_mean,classic_max,classic_worst_x,lame_bound"
+        };
+        writeln!(out, "{}", header).unwrap();
+    }
+    let (mut binary, mut classic) = (Stats::new("Binary"), Stats::new("Classic"));
+    for n in (n_min | 1..=n_max).step_by(2) {
+        // Lame's bound for n, and the sums and the maxima with their x for
+        // both algorithms
+        let lame = fib.iter().rposition(|&f| f <= n).unwrap() as u32 - 2;
+        let (mut sums, mut max) = ([0u64; 2], [(0u32, 0i64); 2]);
+        for x in 1..n {
+            let k = [binary::iterations(x, n), classic::iterations(x, n)];
+            assert!(k[0] < 2 * bits(n) && k[1] <= lame, "A bound is broken for x = {}, n = {}!", x, n);
+            binary.add(k[0], x, n);
+            classic.add(k[1], x, n);
+            for i in 0..2 {
+                sums[i] += k[i] as u64;
+                if k[i] > max[i].0 { max[i] = (k[i], x); }
+            }
+            if let (Some(out), true) = (out.as_mut(), per_input) { writeln!(out, "{},{},{},{}", x, n, k[0], k[1]).unwrap(); }
+        }
+        if let (Some(out), false) = (out.as_mut(), per_input) {
+            let mean = |i: usize| sums[i] as f64 / (n - 1) as f64;
+            writeln!(out, "{},{:.4},{},{},{:.4},{:.4},{},{},{}", n, mean(0), max[0].0, max[0].1, 2.0 * (n as f64).log2(),
+                mean(1), max[1].0, max[1].1, lame).unwrap();
+        }
+    }
+    binary.report();
+    classic.report();
+}
@@ -0,0 +1,219 @@
+// The multi-limb unsigned integers [u64; N], the least significant limb
+// first, together with their arithmetic and the binary mod_inv of "Proof and
+// Implementation of Multi-Limb Binary Euclidean Inversion.rs" with its proof.
+// The files working with such integers include this one into a module:
+//   #[allow(dead_code)]
+//   mod limbs { include!("MultiLimb.rs"); }
+//   use limbs::*;
+// and choose the number of limbs N for their moduli
+include!("InversionError.rs");
+include!("SplitMix64.rs");
+
+// Returns the value x as N limbs
+pub fn small<const N: usize>(x: u64) -> [u64; N] {
+    let mut r = [0; N];
+    r[0] = x;
+    r
+}
+
+pub fn is_zero<const N: usize>(a: &[u64; N]) -> bool {
+    a.iter().all(|&l| l == 0)
+}
+
+pub fn is_odd<const N: usize>(a: &[u64; N]) -> bool {
+    a[0] & 1 == 1
+}
+
+// Returns true if a >= b
+pub fn ge<const N: usize>(a: &[u64; N], b: &[u64; N]) -> bool {
+    for i in (0..N).rev() {
+        if a[i] != b[i] { return a[i] > b[i]; }
+    }
+    true
+}
+
+// Returns a + b mod 2^(64N) and the carry out of the most significant limb
+pub fn add<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], bool) {
+    let (mut r, mut carry) = ([0; N], false);
+    for i in 0..N {
+        let (s1, c1) = a[i].overflowing_add(b[i]);
+        let (s2, c2) = s1.overflowing_add(carry as u64);
+        (r[i], carry) = (s2, c1 | c2);
+    }
+    (r, carry)
+}
+
+// Returns a - b mod 2^(64N) and the borrow out of the most significant limb
+pub fn sub<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], bool) {
+    let (mut r, mut borrow) = ([0; N], false);
+    for i in 0..N {
+        let (d1, b1) = a[i].overflowing_sub(b[i]);
+        let (d2, b2) = d1.overflowing_sub(borrow as u64);
+        (r[i], borrow) = (d2, b1 | b2);
+    }
+    (r, borrow)
+}
+
+// Returns (a + top * 2^(64N)) / 2, i.e. shifts the (64N + 1)-bit value,
+// whose most significant bit is top, right by one bit
+pub fn shr1<const N: usize>(a: &[u64; N], top: bool) -> [u64; N] {
+    let mut r = [0; N];
+    for i in 0..N {
+        let next = if i + 1 < N { a[i + 1] } else { top as u64 };
+        r[i] = (a[i] >> 1) | (next << 63);
+    }
+    r
+}
+
+// Returns the number of significant bits of a
+pub fn bits<const N: usize>(a: &[u64; N]) -> usize {
+    for i in (0..N).rev() {
+        if a[i] != 0 { return 64 * i + 64 - a[i].leading_zeros() as usize; }
+    }
+    0
+}
+
+pub fn bit<const N: usize>(a: &[u64; N], i: usize) -> bool {
+    (a[i / 64] >> (i % 64)) & 1 == 1
+}
+
+// Computes a + b mod n for a, b < n; the carry out is accounted for, since
+// a + b < 2n may exceed 2^(64N) - 1 when n uses all the bits
+pub fn add_mod<const N: usize>(a: &[u64; N], b: &[u64; N], n: &[u64; N]) -> [u64; N] {
+    let (s, carry) = add(a, b);
+    if carry || ge(&s, n) { sub(&s, n).0 } else { s }
+}
+
+// Computes a - b mod n for a, b < n
+pub fn sub_mod<const N: usize>(a: &[u64; N], b: &[u64; N], n: &[u64; N]) -> [u64; N] {
+    let (d, borrow) = sub(a, b);
+    if borrow { add(&d, n).0 } else { d }
+}
+
+// Computes a * b mod n for a, b < n by the double-and-add method
+pub fn mul_mod<const N: usize>(a: &[u64; N], b: &[u64; N], n: &[u64; N]) -> [u64; N] {
+    let mut r = [0; N];
+    for i in (0..bits(b)).rev() {
+        r = add_mod(&r, &r, n);
+        if bit(b, i) { r = add_mod(&r, a, n); }
+    }
+    r
+}
+
+// Computes x^e mod n for x < n by the square-and-multiply method
+pub fn pow_mod<const N: usize>(x: &[u64; N], e: &[u64; N], n: &[u64; N]) -> [u64; N] {
+    let mut r = small(1);
+    for i in (0..bits(e)).rev() {
+        r = mul_mod(&r, &r, n);
+        if bit(e, i) { r = mul_mod(&r, x, n); }
+    }
+    r
+}
+
+// Computes the multiplicative inverse of x modulo n by applying the binary
+// Extended Euclidean Algorithm to N-limb numbers. Unlike mod_inv_unchecked,
+// checks all the preconditions of the method and returns the reason of the
+// failure instead of a wrong value, as the single-word binary mod_inv does.
+// x may be greater than or equal to n; x = 0 is rejected as NonPositive.
+// There is no ModulusTooLarge, since the carries are never lost
+pub fn mod_inv<const N: usize>(x: &[u64; N], n: &[u64; N]) -> Result<[u64; N], InversionError<[u64; N]>> {
+    if !ge(n, &small(2)) { return Err(InversionError::ModulusTooSmall); }
+    if !is_odd(n) { return Err(InversionError::EvenModulus); }
+    if is_zero(x) { return Err(InversionError::NonPositive); }
+    // Due to (5) of binary_loop, which does not rely on GCD(x, n) = 1, after
+    // the loop b = GCD(x, n), and if it is 1, then v is the inverse of x due
+    // to (4)
+    let (b, v) = binary_loop(x, n, &small(1));
+    if b == small(1) { Ok(v) } else { Err(InversionError::NotCoprime { gcd: b }) }
+}
+
+// Computes the multiplicative inverse of x modulo n by applying the binary
+// Extended Euclidean Algorithm to N-limb numbers. For applying this method
+// n must be odd, x and n must be coprime (because if they are not coprime,
+// the inverse does not exist), both x and n must be positive. None of this
+// is checked, and for the inputs violating these conditions the returned
+// value is not the inverse
+pub fn mod_inv_unchecked<const N: usize>(x: &[u64; N], n: &[u64; N]) -> [u64; N] {
+    binary_loop(x, n, &small(1)).1
+}
+
+// Runs the loop of the binary Extended Euclidean Algorithm for x and n with
+// the coefficient u starting at y, 0 <= y < n, and returns the final b and v;
+// y = 1 gives the inverse, as in the single-word binary_loop. n must be odd
+// and x positive; x may be greater than or equal to n, and x and n need not
+// be coprime
+pub fn binary_loop<const N: usize>(x: &[u64; N], n: &[u64; N], y: &[u64; N]) -> ([u64; N], [u64; N]) {
+    let (mut a, mut b, mut u, mut v) = (*x, *n, *y, [0; N]);
+    // Now a = x, b = n;
+    // (1) b is odd; (2) u < n and v < n; (3) y * a = u * x (mod n);
+    // (4) y * b = v * x (mod n); (5) GCD(a, b) = GCD(x, n);
+    // (6) a, b, u and v are non-negative.
+    // (3) holds, since u = y and a = x, and (4) holds, since b = n = 0 (mod n)
+    // and v = 0.
+    // The algorithm and the proof are the same as for the single-word binary
+    // mod_inv, the only difference being that the limbs are unsigned, so (6)
+    // holds by construction and we have to show instead that no intermediate
+    // value exceeds 2^(64N) - 1 or, if it does, that the carry is not lost.
+    // In each iteration we perform the transformation of a and b as well as
+    // their accompanying coefficients u and v, which preserves (1)-(6) and
+    // decreases a + b. Thus, when a is 0, b = GCD(x, n) due to (5), and if it
+    // is 1, then v * x = y (mod n) due to (4), so for y = 1 v is the inverse
+    // of x modulo n. Also, 0 <= v < n due to (2) and (6). For x >= n the
+    // first iterations only subtract n from a, and since a and b never grow,
+    // nothing else changes
+    while !is_zero(&a) {
+        if is_odd(&a) {
+            // Both a and b are odd here. We decrease the greatest by the
+            // smallest and satisfy (5), because GCD(p, q) = GCD(p - q, q),
+            // update the greatest's accompanying coefficient to satisfy
+            // (3) and (4), swap the values for a and b as well as for their
+            // accompanying coefficients, if this is required, to satisfy (1)
+            // without breaking (3)-(5). Since a and b never grow, they stay
+            // below 2^(64N) and both subtractions are exact
+            let (d, borrow) = if ge(&a, &b) {
+                a = sub(&a, &b).0;
+                sub(&u, &v)
+            } else {
+                (a, b) = (sub(&b, &a).0, a);
+                let d = sub(&v, &u);
+                v = u;
+                d
+            };
+            // Here d = u - v (or v - u) mod 2^(64N) and, due to (2), the true
+            // difference lies in (-n, n). If it is negative, borrow is set
+            // and we add n; the sum overflows 2^(64N) exactly once, and the
+            // result mod 2^(64N) is the true value u - v + n, which lies in
+            // [0, n). Thus, (2) and (6) are satisfied without breaking (1)-(5)
+            u = if borrow { add(&d, n).0 } else { d };
+        }
+        // Here a is even and (1)-(6) are satisfied. We divide a by 2 and still
+        // satisfy (5), since b is odd due to (1) and GCD(p, q) = GCD(p / 2, q)
+        // for even p and odd q. As the result, only (3) is not satisfied
+        a = shr1(&a, false);
+        // In order to satisfy (3) without breaking (1)-(2) and (4)-(6),
+        // u should be set to u * 2^(-1) mod n. If u is even, it is done by
+        // dividing u by 2. For odd u we set u to (u + n) / 2, since n is odd.
+        // The sum u + n is below 2n < 2^(64N + 1), so it fits in N limbs and
+        // the carry; we shift the carry back in, and (u + n) / 2 < n
+        // satisfies (2)
+        u = if is_odd(&u) {
+            let (s, carry) = add(&u, n);
+            shr1(&s, carry)
+        } else {
+            shr1(&u, false)
+        };
+    }
+    (b, v)
+}
+
+// Returns a pseudo-random value in [1, n) for n > 1
+pub fn random_below<const N: usize>(n: &[u64; N], state: &mut u64) -> [u64; N] {
+    let top = bits(n);
+    loop {
+        let mut r = [0; N];
+        for (i, limb) in r.iter_mut().enumerate() {
+            if 64 * i < top { *limb = next_u64(state) >> (64 * (i + 1)).saturating_sub(top); }
+        }
+        if !is_zero(&r) && !ge(&r, n) { return r; }
+    }
+}
@@ -0,0 +1,118 @@
+/*
+       The Batch Modular Inversion by Means of Montgomery's Trick:
+                    Implementation in Rust and Proof
+
+                             October 2026
+*/
+// The classic mod_inv of "Proof and Implementation of Euclidean Inversion.rs"
+// and the GCD of its ext_gcd. The batch inversion performs a single call to
+// mod_inv for the whole batch, or two, if some entry is not coprime with n
+#[allow(dead_code)]
+mod classic {
+    include!("Proof and Implementation of Euclidean Inversion.rs");
+
+    pub fn inv(x: i64, n: i64) -> Result<i64, InversionError> {
+        mod_inv(x, n)
+    }
+
+    pub fn gcd(x: i64, n: i64) -> i64 {
+        ext_gcd(x, n).0
+    }
+}
+
+use classic::InversionError;
+
+// Computes a * b mod n for 0 <= a, b < n without overflowing i64
+fn mul_mod(a: i64, b: i64, n: i64) -> i64 {
+    (a as i128 * b as i128 % n as i128) as i64
+}
+
+// Computes the multiplicative inverses of all xs modulo n by applying
+// Montgomery's trick: for k non-zero entries modulo n, 3 * k multiplications
+// (k for the prefix products and 2 * k on the way back) and a single call to
+// mod_inv instead of k calls to mod_inv; panics in the case of n < 2. The
+// result has the same length as xs. The entries, which are 0 modulo n, are
+// skipped, and None is returned for them instead of poisoning the product.
+// If n is composite, an entry may be non-zero, but still not coprime with n;
+// then the product is not invertible, and mod_inv returns g = GCD(p, n)
+// instead. Each entry r, which is not coprime with n, shares a factor d > 1
+// with g, since d divides both n and the product, and each entry sharing a
+// factor with g is not coprime with n, since g divides n. Thus, k GCDs with
+// g find exactly the non-invertible entries, which are skipped as the zero
+// ones, and the trick is repeated for the rest with k more multiplications
+// and one more call to mod_inv, which succeeds. For a prime n this never
+// happens
+fn batch_inv(xs: &[i64], n: i64) -> Vec<Option<i64>> {
+    if n < 2 { panic!("The modulus must be greater than 1!"); }
+    let mut residues: Vec<i64> = xs.iter().map(|&x| x.rem_euclid(n)).collect();
+    // prefix[i] is the product of the non-zero residues before the i-th one
+    let mut prefix = vec![0; xs.len()];
+    let mut p_inv = loop {
+        let mut p = 1;
+        for (i, &r) in residues.iter().enumerate() {
+            if r != 0 {
+                prefix[i] = p;
+                p = mul_mod(p, r, n);
+            }
+        }
+        // Now p is the product of all the non-zero residues. It is invertible
+        // if and only if each of them is invertible, since GCD(a * b, n) = 1
+        // if and only if GCD(a, n) = 1 and GCD(b, n) = 1
+        match classic::inv(p, n) {
+            Ok(p_inv) => break p_inv,
+            Err(InversionError::NotCoprime { gcd }) => {
+                for r in residues.iter_mut() {
+                    if classic::gcd(*r, gcd) > 1 { *r = 0; }
+                }
+            }
+            Err(e) => unreachable!("The classic algorithm does not return {:?} for n >= 2!", e),
+        }
+    };
+    // Going backwards, before processing the i-th entry we have p_inv equal
+    // to the inverse of the product of the non-zero residues up to the i-th
+    // one inclusive. Thus, p_inv * prefix[i] is the inverse of the i-th
+    // residue, and p_inv * r is the inverse of the product up to the
+    // previous non-zero residue, which keeps the statement above true
+    let mut result = vec![None; xs.len()];
+    for (i, &r) in residues.iter().enumerate().rev() {
+        if r != 0 {
+            result[i] = Some(mul_mod(p_inv, prefix[i], n));
+            p_inv = mul_mod(p_inv, r, n);
+        }
+    }
+    result
+}
+
+include!("SplitMix64.rs");
+
+fn main() {
+    // The Goldilocks prime 2^64 - 2^32 + 1 does not fit in i64, so we take the
+    // largest prime below 2^62 and the Mersenne prime 2^31 - 1; every tenth
+    // entry is a multiple of the modulus, including negative ones
+    let mut state = 2023;
+    for n in [(1 << 62) - 57, (1 << 31) - 1] {
+        let xs: Vec<i64> = (0..10_000)
+            .map(|i| if i % 10 == 0 { n * (i % 3 - 1) } else { next_u64(&mut state) as i64 })
+            .collect();
+        let inverses = batch_inv(&xs, n);
+        for (x, i) in xs.iter().zip(inverses) {
+            assert!(i == classic::inv(*x, n).ok(), "Mismatch with mod_inv for {} mod {}!", x, n);
+        }
+    }
+    // Composite modulus: 5 and 4 are not coprime with 10, and 0 is skipped
+    let (xs, n) = ([3, 0, 5, 7, 4, -1], 10);
+    let inverses = batch_inv(&xs, n);
+    assert!(inverses == [Some(7), None, None, Some(3), None, Some(9)], "Incorrect inverses!");
+    assert!(batch_inv(&[], n).is_empty(), "Incorrect inverses!");
+    // Random entries modulo composites, including the product of two primes
+    // near 2^31, which makes the non-invertible entries rare, and entries
+    // near i64::MIN and i64::MAX
+    for n in [3 * 5 * 7 * 11 * 13, 2147483647 * 2147483629, i64::MAX] {
+        let mut xs: Vec<i64> = (0..1000).map(|_| next_u64(&mut state) as i64).collect();
+        xs.extend([i64::MIN, i64::MIN + 1, i64::MAX, 2147483647 * 12345, n - 7]);
+        for (x, i) in xs.iter().zip(batch_inv(&xs, n)) {
+            assert!(i == classic::inv(*x, n).ok(), "Mismatch with mod_inv for {} mod {}!", x, n);
+        }
+    }
+    println!("{:?}", inverses);
+}
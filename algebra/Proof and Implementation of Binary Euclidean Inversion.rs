@@ -5,25 +5,69 @@ The Modular Inversion by Means of the Binary Extended Euclidean Algorithm:
                              Aleksei Vambol
                                June 2023
 */
+include!("InversionError.rs");
+
+// Computes the multiplicative inverse of x modulo n by applying the binary
+// Extended Euclidean Algorithm. Unlike mod_inv_unchecked, checks all the
+// preconditions of the method and returns the reason of the failure instead
+// of a wrong value
+fn mod_inv(x: i64, n: i64) -> Result<i64, InversionError> {
+    if n < 2 { return Err(InversionError::ModulusTooSmall); }
+    if n & 1 == 0 { return Err(InversionError::EvenModulus); }
+    if x <= 0 { return Err(InversionError::NonPositive); }
+    if n >= 1 << 62 { return Err(InversionError::ModulusTooLarge); }
+    let (b, v, _) = binary_loop(x, n, 1);
+    // Due to (5) of binary_loop, which does not rely on GCD(x, n) = 1, after
+    // the loop b = GCD(x, n), and if it is 1, then v is the inverse of x due
+    // to (4) for y = 1, as in mod_inv_unchecked
+    if b == 1 { Ok(v) } else { Err(InversionError::NotCoprime { gcd: b }) }
+}
+
 // Computes the multiplicative inverse of x modulo n by applying the binary  
 // Extended Euclidean Algorithm. For applying this method n must be odd,   
 // x and n must be coprime (because if they are not comprime, the inverse  
-// does not exist), both x and n must be positive
-fn mod_inv(x: i64, n: i64) -> i64 {
-    let (mut a, mut b, mut u, mut v) = (x, n, 1, 0);
+// does not exist), both x and n must be positive, and n must be less than
+// 2^62, since u + n < 2n must not overflow. None of this is checked, and for
+// the inputs violating these conditions the returned value is not the inverse
+fn mod_inv_unchecked(x: i64, n: i64) -> i64 {
+    binary_loop(x, n, 1).1
+}
+
+// Runs the loop of the binary Extended Euclidean Algorithm for x and n with
+// the coefficient u starting at y, 0 <= y < n, and returns the final b and v
+// together with the number of iterations; y = 1 gives the inverse
+// (mod_inv_unchecked), and y = y' gives the quotient y' / x (mod_div). The
+// preconditions are those of mod_inv_unchecked
+fn binary_loop(x: i64, n: i64, y: i64) -> (i64, i64, u32) {
+    let (mut a, mut b, mut u, mut v, mut iterations) = (x, n, y, 0, 0);
     // Now a = x, b = n;
-    // (1) b is odd; (2) u < n and v < n; (3) a = u * x (mod n);  
-    // (4) b = v * x (mod n); (5) GCD(a, b) = GCD(x, n) = 1; 
+    // (1) b is odd; (2) u < n and v < n; (3) y * a = u * x (mod n);  
+    // (4) y * b = v * x (mod n); (5) GCD(a, b) = GCD(x, n); 
     // (6) a, b, u and v are non-negative. 
+    // (3) holds, since u = y and a = x, and (4) holds, since b = n = 0 (mod n)
+    // and v = 0. For y = 1 (3) and (4) are a = u * x and b = v * x (mod n).
     // In each iteration we perform the transformation of a and b as well as  
     // their accompanying coefficients u and v, which preserves (1)-(6) and 
-    // decreases a + b. Thus, when a is 0, b is 1 due to (5), so v is the 
-    // inverse of x modulo n due to (4). Also, 0 < v < n due to (2) and (6). 
+    // decreases a + b. Thus, when a is 0, b = GCD(x, n) due to (5), and if it
+    // is 1, then v * x = y (mod n) due to (4), so for y = 1 v is the inverse
+    // of x modulo n. Also, 0 <= v < n due to (2) and (6), and v > 0 for y > 0. 
     // In the case of the classic Extended Euclidean Algorithm we would have 
-    // a = u * x + i * n and b = v * x + j * n instead of (3) and (4), only (5) 
-    // would still hold true. Since we do not seek all the Bezout coefficients 
-    // and have "(mod n)" in both (3) and (4), we discard "i * n" and "j * n" 
+    // y * a = u * x + i * n and y * b = v * x + j * n instead of (3) and (4),
+    // only (5) would still hold true. Since we do not seek all the Bezout
+    // coefficients and have "(mod n)" in both (3) and (4), we discard "i * n"
+    // and "j * n". In the proof mode i and j are kept as in ext_gcd, and (1)-(6)
+    // with the exact (3) and (4) are checked in every iteration.
+    // The number of iterations does not exceed bits(x) + bits(n) - 1. Let
+    // P = bits(a) + bits(b). An iteration with even a halves a. For odd
+    // a >= b, a becomes (a - b) / 2 < a / 2. For odd a < b, (a, b) becomes
+    // ((b - a) / 2, a), where (b - a) / 2 < b / 2. In each case P decreases
+    // by at least 1; it starts at bits(x) + bits(n), and after the loop a = 0
+    // and b >= 1, so P >= 1
+    #[cfg(feature = "proof")]
+    let (mut i, mut j) = (0i128, y as i128);
     while a > 0 {
+        #[cfg(feature = "proof")]
+        check_invariants((x, n, y), (a, u, i), (b, v, j));
         if (a & 1) > 0 {
             // Both a and b are odd here. We decrease the greatest by the   
             // smallest and satisfy (5), because GCD(p, q) = GCD(p - q, q),  
@@ -33,11 +77,19 @@ fn mod_inv(x: i64, n: i64) -> i64 {
             // without breaking (3)-(5). Thus, (1)-(5) are satisfied
             if a >= b {
                 (a, u) = (a - b, u - v);
+                #[cfg(feature = "proof")]
+                { i -= j; }
             } else {
                 (a, b, u, v) = (b - a, a, v - u, u);
+                #[cfg(feature = "proof")]
+                { (i, j) = (j - i, i); }
             }
             // We conditionally update u to satisfy (6) without breaking (1)-(5)
-            if u < 0 { u += n; }
+            if u < 0 {
+                u += n;
+                #[cfg(feature = "proof")]
+                { i -= x as i128; }
+            }
         }
         // Here a is even and (1)-(6) are satisfied. We divide a by 2 and still 
         // satisfy (5), since b is odd due to (1) and GCD(p, q) = GCD(p / 2, q) 
@@ -46,16 +98,154 @@ fn mod_inv(x: i64, n: i64) -> i64 {
         // In order to satisfy (3) without breaking (1)-(2) and (4)-(6),   
         // u should be set to u * 2^(-1) mod n. If u is even, it is done by  
         // dividing u by 2. For odd u we set u to (u + n) / 2, since n is odd, 
-        // u < n due to (2) and u is non-negative due to (6)
-        if u & 1 > 0 { u += n; }
-        u = u >> 1;
+        // u < n due to (2) and u is non-negative due to (6); u + n < 2^63
+        // for n < 2^62
+        if u & 1 > 0 {
+            u += n;
+            #[cfg(feature = "proof")]
+            { i -= x as i128; }
+        }
+        u >>= 1;
+        #[cfg(feature = "proof")]
+        { i >>= 1; }
+        iterations += 1;
+    }
+    #[cfg(feature = "proof")]
+    check_invariants((x, n, y), (a, u, i), (b, v, j));
+    (b, v, iterations)
+}
+
+// Asserts the invariants (1)-(6) of binary_loop for its inputs x, n and y and
+// the triples (a, u, i) and (b, v, j), where (3) and (4) are exact:
+// y * a = u * x + i * n and y * b = v * x + j * n, as in ext_gcd for y = 1.
+// Compiled only in the proof mode, which is enabled by --cfg 'feature="proof"'
+#[cfg(feature = "proof")]
+fn check_invariants((x, n, y): (i64, i64, i64), (a, u, i): (i64, i64, i128), (b, v, j): (i64, i64, i128)) {
+    let gcd = |mut p: i64, mut q: i64| {
+        while q > 0 { (p, q) = (q, p % q); }
+        p
+    };
+    let combine = |c_x: i64, c_n: i128| c_x as i128 * x as i128 + c_n * n as i128;
+    assert!(b & 1 == 1, "Invariant (1) is broken!");
+    assert!(u < n && v < n, "Invariant (2) is broken!");
+    assert!(y as i128 * a as i128 == combine(u, i), "Invariant (3) is broken!");
+    assert!(y as i128 * b as i128 == combine(v, j), "Invariant (4) is broken!");
+    assert!(gcd(a, b) == gcd(x, n), "Invariant (5) is broken!");
+    assert!(a >= 0 && b >= 0 && u >= 0 && v >= 0, "Invariant (6) is broken!");
+}
+
+// Computes y * x^(-1) mod n by applying the binary Extended Euclidean
+// Algorithm, which is faster than multiplying y by the result of mod_inv.
+// The preconditions are the same as for mod_inv, and so are the errors; y
+// may be any integer
+fn mod_div(y: i64, x: i64, n: i64) -> Result<i64, InversionError> {
+    if n < 2 { return Err(InversionError::ModulusTooSmall); }
+    if n & 1 == 0 { return Err(InversionError::EvenModulus); }
+    if x <= 0 { return Err(InversionError::NonPositive); }
+    if n >= 1 << 62 { return Err(InversionError::ModulusTooLarge); }
+    // This is binary_loop with u starting at y' = y mod n instead of 1, so
+    // after it b = GCD(x, n), and if it is 1, then v * x = y' (mod n) due to
+    // (4), so v is the quotient, and 0 <= v < n
+    let (b, v, _) = binary_loop(x, n, y.rem_euclid(n));
+    if b == 1 { Ok(v) } else { Err(InversionError::NotCoprime { gcd: b }) }
+}
+
+// Computes g = GCD(x, n) together with the Bezout coefficients c_x and c_n, 
+// such that g = c_x * x + c_n * n, by applying the binary Extended Euclidean 
+// Algorithm to non-negative x and odd n >= 3, both less than 2^62. The 
+// coefficients satisfy 0 <= c_x < n and -x < c_n <= 1
+fn ext_gcd(x: i64, n: i64) -> (i64, i64, i64) {
+    let (mut a, mut b, mut u, mut v, mut i, mut j) = (x, n, 1, 0, 0, 1);
+    // Now a = x, b = n; this is binary_loop for y = 1, which also 
+    // keeps the coefficients of n discarded there. We have 
+    // (1) b is odd; (2) u < n and v < n; (3) a = u * x + i * n; 
+    // (4) b = v * x + j * n; (5) GCD(a, b) = GCD(x, n); 
+    // (6) a, b, u and v are non-negative. 
+    // Whenever u and v are updated, i and j are updated in the same way, and 
+    // whenever n is added to u, x is subtracted from i, which keeps (3) and 
+    // (4) exact. Due to (3) i = (a - u * x) / n, where 0 <= a <= max(x, n) 
+    // and 0 <= u < 2n even before halving, so |i| <= 2x + 1, and the same is 
+    // true for j; thus, no value overflows for x, n < 2^62
+    while a > 0 {
+        #[cfg(feature = "proof")]
+        check_invariants((x, n, 1), (a, u, i as i128), (b, v, j as i128));
+        if (a & 1) > 0 {
+            if a >= b {
+                (a, u, i) = (a - b, u - v, i - j);
+            } else {
+                (a, b, u, v, i, j) = (b - a, a, v - u, u, j - i, i);
+            }
+            if u < 0 { (u, i) = (u + n, i - x); }
+        }
+        a >>= 1;
+        // Here a is even. If u is odd, we add n to u and subtract x from i, 
+        // so u is even now. Then i * n = a - u * x is even, and i is even, 
+        // since n is odd. Thus, halving a, u and i preserves (3) exactly
+        if u & 1 > 0 { (u, i) = (u + n, i - x); }
+        (u, i) = (u >> 1, i >> 1);
     }
-    v
+    #[cfg(feature = "proof")]
+    check_invariants((x, n, 1), (a, u, i as i128), (b, v, j as i128));
+    // Now a = 0 and b = GCD(x, n) = g due to (5), since halving a does not 
+    // change the GCD for odd b. Due to (4) j = (g - v * x) / n, and since 
+    // 0 <= v < n and 0 < g <= n, we have -x < j <= 1
+    (b, v, j)
 }
 
 fn main() {
+    if cfg!(feature = "proof") { println!("Proof mode: the invariants are checked in every iteration"); }
     let (x, n) = (13, 97);
-    let i = mod_inv(x, n);
+    let i = mod_inv_unchecked(x, n);
     assert!((i * x) % n == 1, "Incorrect inverse!");
+    assert!(mod_inv(x, n) == Ok(i), "Incorrect inverse!");
+    assert!(mod_inv(x, 1) == Err(InversionError::ModulusTooSmall), "Incorrect error!");
+    assert!(mod_inv(x, 96) == Err(InversionError::EvenModulus), "Incorrect error!");
+    assert!(mod_inv(-x, n) == Err(InversionError::NonPositive), "Incorrect error!");
+    assert!(mod_inv(6, 15) == Err(InversionError::NotCoprime { gcd: 3 }), "Incorrect error!");
+    assert!(mod_inv(30, 15) == Err(InversionError::NotCoprime { gcd: 15 }), "Incorrect error!");
+    // The largest prime modulus below 2^62, where u + n comes close to 2^63,
+    // and a prime modulus near i64::MAX, where it would overflow
+    let n = (1 << 62) - 57;
+    for x in [1, 2, 3, n - 2, n - 1, i64::MAX] {
+        let v = mod_inv(x, n).unwrap();
+        assert!(0 < v && v < n && v as i128 * x as i128 % n as i128 == 1, "Incorrect inverse!");
+    }
+    assert!(mod_inv(3, i64::MAX - 24) == Err(InversionError::ModulusTooLarge), "Incorrect error!");
+    // The division agrees with the multiplication by the inverse
+    for n in (3..300i64).step_by(2) {
+        for x in 1..300i64 {
+            for y in [-n - 1, -1, 0, 1, 2, x, n - 1, 1000] {
+                let expected = mod_inv(x, n).map(|i| (y.rem_euclid(n) * i) % n);
+                assert!(mod_div(y, x, n) == expected, "Incorrect quotient!");
+            }
+        }
+    }
+    assert!(mod_div(5, 6, 15) == Err(InversionError::NotCoprime { gcd: 3 }), "Incorrect error!");
+    assert!(mod_div(5, x, 96) == Err(InversionError::EvenModulus), "Incorrect error!");
+    assert!(mod_div(7, 3, i64::MAX - 24) == Err(InversionError::ModulusTooLarge), "Incorrect error!");
+    let n = (1 << 62) - 57;
+    let q = mod_div(7, 3, n).unwrap();
+    assert!(0 <= q && q < n && q as i128 * 3 % n as i128 == 7, "Incorrect quotient!");
+    let (y, x, n) = (i64::MAX, (1 << 62) - 2, (1 << 62) - 1);
+    let q = mod_div(y, x, n).unwrap();
+    assert!(q as i128 * x as i128 % n as i128 == y as i128 % n as i128, "Incorrect quotient!");
+    // The bound on the number of iterations for all small pairs
+    let bits = |x: i64| i64::BITS - x.leading_zeros();
+    for n in (3..300i64).step_by(2) {
+        for x in 1..300i64 {
+            assert!(binary_loop(x, n, 1).2 < bits(x) + bits(n), "Too many iterations!");
+        }
+    }
+    // The Bezout coefficients and their bounds for all small pairs
+    for n in (3..300i64).step_by(2) {
+        for x in 0..300i64 {
+            let (g, c_x, c_n) = ext_gcd(x, n);
+            assert!(n % g == 0 && x % g == 0 && g == c_x * x + c_n * n, "Incorrect GCD!");
+            assert!(0 <= c_x && c_x < n && -x < c_n && c_n <= 1, "Incorrect bounds!");
+        }
+    }
+    let (x, n) = ((1 << 62) - 2, (1 << 62) - 1);
+    let (g, c_x, c_n) = ext_gcd(x, n);
+    assert!(g == 1 && g as i128 == c_x as i128 * x as i128 + c_n as i128 * n as i128, "Incorrect GCD!");
     println!("{}", i);
 }
@@ -0,0 +1,205 @@
+/*
+  The Inversion in the Binary Fields GF(2^m) by Means of the Binary Extended
+     Euclidean Algorithm and Itoh-Tsujii: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// The elements of GF(2^m) = GF(2)[X] / (f) for an irreducible f of degree m
+// are the polynomials over GF(2) of degree below m, stored as bit strings:
+// bit i is the coefficient of X^i. The addition is XOR, and the binary
+// mod_inv carries over almost verbatim: "odd" means that the constant term is
+// 1, the division by 2 is the division by X, and the comparison of values
+// is the comparison of degrees. Itoh-Tsujii computes x^(-1) = x^(2^m - 2)
+// by an addition chain for m - 1 with about log(m) multiplications and
+// m - 1 squarings. The squaring is linear over GF(2), so it can be made much
+// cheaper than a multiplication; here it is a plain multiplication
+
+// The supported degrees go up to 571 (NIST B-571); bit m of the modulus
+// needs one more bit
+const WORDS: usize = 9;
+
+// An element or a polynomial of degree at most 64 * WORDS - 1
+type Elem = [u64; WORDS];
+
+const ZERO: Elem = [0; WORDS];
+const ONE: Elem = [1, 0, 0, 0, 0, 0, 0, 0, 0];
+
+// The field GF(2^m) defined by an irreducible polynomial f of degree m, which
+// is not checked
+struct BinaryField {
+    m: usize,
+    f: Elem,
+}
+
+impl BinaryField {
+    // The field defined by f = X^m + X^k_1 + ... + 1 for the given exponents
+    // [m, k_1, ..., 0]
+    fn new(exponents: &[usize]) -> BinaryField {
+        let mut f = ZERO;
+        for &e in exponents { f[e / 64] ^= 1 << (e % 64); }
+        BinaryField { m: exponents[0], f }
+    }
+
+    // Returns a * X mod f for deg a < m
+    fn mul_x(&self, a: &Elem) -> Elem {
+        let r = shl1(a);
+        if bit(&r, self.m) { xor(&r, &self.f) } else { r }
+    }
+
+    fn mul(&self, a: &Elem, b: &Elem) -> Elem {
+        let mut r = ZERO;
+        for i in (0..self.m).rev() {
+            r = self.mul_x(&r);
+            if bit(b, i) { r = xor(&r, a); }
+        }
+        r
+    }
+
+    // Returns a^(2^k)
+    fn square_n(&self, a: &Elem, k: usize) -> Elem {
+        (0..k).fold(*a, |r, _| self.mul(&r, &r))
+    }
+
+    // Computes the multiplicative inverse of x != 0 by applying the binary
+    // Extended Euclidean Algorithm
+    fn inv(&self, x: &Elem) -> Elem {
+        let (mut a, mut b, mut u, mut v) = (*x, self.f, ONE, ZERO);
+        // Now a = x, b = f; this is the loop of the binary mod_inv with
+        // (1) the constant term of b is 1; (2) deg u < m and deg v < m;
+        // (3) a = u * x (mod f); (4) b = v * x (mod f); (5) GCD(a, b) = 1.
+        // There are no negative values, so there is no (6). Each iteration
+        // decreases deg a + deg b, so a becomes 0, b = 1 due to (5) and (1),
+        // and v is the inverse of x due to (4)
+        while a != ZERO {
+            if a[0] & 1 == 1 {
+                // Both constant terms are 1, so a + b is divisible by X.
+                // Adding the polynomial of the smaller degree to the other one
+                // does not increase the degree and keeps (1)-(5) as in the
+                // binary mod_inv; since -1 = 1, u - v is u + v
+                if degree(&a) < degree(&b) { (a, b, u, v) = (b, a, v, u); }
+                (a, u) = (xor(&a, &b), xor(&u, &v));
+            }
+            // We divide a by X, which keeps (5) due to (1), and set u to
+            // u * X^(-1) mod f: if the constant term of u is 1, we add f,
+            // whose constant term is 1 as well. Then deg (u + f) = m, so the
+            // quotient has degree m - 1 and (2) holds
+            a = shr1(&a);
+            if u[0] & 1 == 1 { u = xor(&u, &self.f); }
+            u = shr1(&u);
+        }
+        v
+    }
+
+    // Computes the multiplicative inverse of x != 0 by applying the
+    // Itoh-Tsujii algorithm
+    fn inv_itoh_tsujii(&self, x: &Elem) -> Elem {
+        // The multiplicative group has order 2^m - 1, so x^(-1) = x^(2^m - 2)
+        // = (x^(2^(m - 1) - 1))^2. Let y_k = x^(2^k - 1); then
+        // y_(i + j) = y_i^(2^j) * y_j and y_(k + 1) = y_k^2 * x. Going through
+        // the bits of m - 1 from the top, we double k with the first formula
+        // and append the next bit with the second one, starting from y_1 = x.
+        // For m = 1, i.e. in GF(2), m - 1 = 0 has no top bit, but the only
+        // non-zero element is 1, which is its own inverse
+        if self.m == 1 { return *x; }
+        let (mut y, mut k) = (*x, 1);
+        for i in (0..usize::BITS - (self.m - 1).leading_zeros() - 1).rev() {
+            y = self.mul(&self.square_n(&y, k), &y);
+            k *= 2;
+            if (self.m - 1) >> i & 1 == 1 {
+                y = self.mul(&self.mul(&y, &y), x);
+                k += 1;
+            }
+        }
+        // Now k = m - 1
+        self.mul(&y, &y)
+    }
+}
+
+fn xor(a: &Elem, b: &Elem) -> Elem {
+    let mut r = ZERO;
+    for i in 0..WORDS { r[i] = a[i] ^ b[i]; }
+    r
+}
+
+fn bit(a: &Elem, i: usize) -> bool {
+    (a[i / 64] >> (i % 64)) & 1 == 1
+}
+
+// Returns the degree of a != 0; the degree of 0 is 0 here, which does not
+// matter, since the degrees are compared only for a, b with constant term 1
+fn degree(a: &Elem) -> usize {
+    for i in (0..WORDS).rev() {
+        if a[i] != 0 { return 64 * i + 63 - a[i].leading_zeros() as usize; }
+    }
+    0
+}
+
+fn shl1(a: &Elem) -> Elem {
+    let mut r = ZERO;
+    for i in 0..WORDS { r[i] = a[i] << 1 | if i > 0 { a[i - 1] >> 63 } else { 0 }; }
+    r
+}
+
+fn shr1(a: &Elem) -> Elem {
+    let mut r = ZERO;
+    for i in 0..WORDS { r[i] = a[i] >> 1 | if i + 1 < WORDS { a[i + 1] << 63 } else { 0 }; }
+    r
+}
+
+include!("SplitMix64.rs");
+
+// Returns a pseudo-random non-zero element of the field
+fn random(field: &BinaryField, state: &mut u64) -> Elem {
+    loop {
+        let mut r = ZERO;
+        for limb in r.iter_mut().take(field.m.div_ceil(64)) { *limb = next_u64(state); }
+        if !field.m.is_multiple_of(64) { r[field.m / 64] &= (1 << (field.m % 64)) - 1; }
+        if r != ZERO { return r; }
+    }
+}
+
+fn main() {
+    // The AES field GF(2^8) = GF(2)[X] / (X^8 + X^4 + X^3 + X + 1), where
+    // the inverse of 0x53 is 0xca, exhaustively
+    let aes = BinaryField::new(&[8, 4, 3, 1, 0]);
+    let mut x = ZERO;
+    for i in 1..256 {
+        x[0] = i;
+        let y = aes.inv(&x);
+        assert!(aes.mul(&x, &y) == ONE && y[0] < 256, "Incorrect inverse in GF(2^8)!");
+        assert!(y == aes.inv_itoh_tsujii(&x), "Mismatch with Itoh-Tsujii in GF(2^8)!");
+    }
+    x[0] = 0x53;
+    assert!(aes.inv(&x)[0] == 0xca, "Incorrect inverse in GF(2^8)!");
+    // The smallest fields GF(2), GF(2^2) and GF(2^3), exhaustively
+    let small: [&[usize]; 3] = [&[1, 0], &[2, 1, 0], &[3, 1, 0]];
+    for exponents in small {
+        let field = BinaryField::new(exponents);
+        for i in 1..1 << field.m {
+            x[0] = i;
+            let y = field.inv(&x);
+            assert!(field.mul(&x, &y) == ONE, "Incorrect inverse in GF(2^{})!", field.m);
+            assert!(y == field.inv_itoh_tsujii(&x), "Mismatch with Itoh-Tsujii in GF(2^{})!", field.m);
+        }
+    }
+    // The NIST binary fields, including the largest one
+    let mut state = 2023;
+    let fields: [&[usize]; 5] = [
+        &[163, 7, 6, 3, 0],
+        &[233, 74, 0],
+        &[283, 12, 7, 5, 0],
+        &[409, 87, 0],
+        &[571, 10, 5, 2, 0],
+    ];
+    for exponents in fields {
+        let field = BinaryField::new(exponents);
+        assert!(field.inv(&ONE) == ONE && field.inv_itoh_tsujii(&ONE) == ONE, "Incorrect inverse of 1!");
+        for _ in 0..100 {
+            let x = random(&field, &mut state);
+            let y = field.inv(&x);
+            assert!(field.mul(&x, &y) == ONE, "Incorrect inverse in GF(2^{})!", field.m);
+            assert!(y == field.inv_itoh_tsujii(&x), "Mismatch with Itoh-Tsujii in GF(2^{})!", field.m);
+        }
+        println!("GF(2^{}): OK", field.m);
+    }
+}
@@ -0,0 +1,186 @@
+/*
+     The Jacobi and Legendre Symbols by Means of the Binary Euclidean
+                   Algorithm: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// Deciding whether x is a square modulo a prime p (for hash-to-curve and the
+// decompression of points) by Euler's criterion x^((p - 1) / 2) costs an
+// exponentiation. The Jacobi symbol (x / n) for odd n > 0 is computed by the
+// same loop as the binary mod_inv: a is halved, and if it is odd, the smaller
+// of a and b is subtracted from the greater one, swapping them when needed.
+// Instead of the coefficients u and v we track the sign of the symbol, which
+// changes according to the rules below. For a prime n the Jacobi symbol is
+// the Legendre symbol: 1 for the non-zero squares, -1 for the non-squares
+// and 0 for the multiples of n.
+// The Jacobi symbol (a / b) for odd b > 0 has the following properties:
+// (J1) (a / b) depends only on a mod b; (J2) (0 / b) = 1 if b = 1, and 0
+// otherwise; (J3) (2a / b) = (2 / b) * (a / b), where (2 / b) = -1 if
+// b mod 8 is 3 or 5, and 1 otherwise; (J4) (quadratic reciprocity) for odd
+// a > 0, (a / b) = -(b / a) if a mod 4 = b mod 4 = 3, and (b / a) otherwise;
+// if GCD(a, b) > 1, both symbols are 0, so this holds true as well
+
+// Computes the Jacobi symbol (x / n) by applying the binary Euclidean
+// algorithm; panics if n is even. Any odd n up to 2^64 - 1 is supported
+fn jacobi(x: u64, n: u64) -> i32 {
+    if n & 1 == 0 { panic!("The modulus must be odd!"); }
+    let (mut a, mut b, mut t) = (x % n, n, 1);
+    // Now a = x mod n, b = n;
+    // (1) b is odd and positive; (2) (x / n) = t * (a / b); (3) a < b or
+    // a is even, so (a / b) is defined for b, which is odd due to (1).
+    // Each iteration halves a and does not increase b, so a * b decreases
+    // until a = 0. Then (x / n) = t * (0 / b), which is t if b = 1, and 0
+    // otherwise due to (J2)
+    while a > 0 {
+        if (a & 1) > 0 {
+            // Both a and b are odd here. If a < b, we swap them and update t
+            // due to (J4), which keeps (1) and (2). Then a - b = a (mod b),
+            // so (a - b) / b = (a / b) due to (J1), which keeps (2)
+            if a < b {
+                if a & b & 3 == 3 { t = -t; }
+                (a, b) = (b, a);
+            }
+            a -= b;
+        }
+        // Here a is even and (1)-(2) are satisfied. Halving a multiplies the
+        // symbol by (2 / b) due to (J3), so t is updated accordingly; a / 2 <
+        // b, so (3) holds. For a = 0 the symbol does not change
+        if a > 0 && (b & 7 == 3 || b & 7 == 5) { t = -t; }
+        a >>= 1;
+    }
+    if b == 1 { t } else { 0 }
+}
+
+// Computes the Legendre symbol (x / p) for an odd prime p, which is 1 if x is
+// a non-zero square modulo p, -1 if it is not a square, and 0 if p divides x.
+// The primality of p is not checked
+fn legendre(x: u64, p: u64) -> i32 {
+    jacobi(x, p)
+}
+
+// The largest supported modulus of the constant-time version is below
+// 2^BITS, so that the differences of a and b have a meaningful sign bit
+const BITS: u32 = 63;
+// Each iteration at least halves a * b < 2^(2 * BITS), and a * b >= 1 as long
+// as a > 0, so a = 0 after this many iterations
+const ITERATIONS: u32 = 2 * BITS;
+
+// Returns all ones if the lowest bit of a is 1, and 0 otherwise
+fn odd_mask(a: u64) -> u64 {
+    (a & 1).wrapping_neg()
+}
+
+// Returns all ones if a < b, and 0 otherwise; requires a, b < 2^63
+fn lt_mask(a: u64, b: u64) -> u64 {
+    ((a.wrapping_sub(b) as i64) >> 63) as u64
+}
+
+// Returns all ones if a > 0, and 0 otherwise; requires a < 2^63
+fn nonzero_mask(a: u64) -> u64 {
+    lt_mask(0, a)
+}
+
+// Returns a if mask is all ones, and b if mask is 0
+fn select(mask: u64, a: u64, b: u64) -> u64 {
+    b ^ (mask & (a ^ b))
+}
+
+// Computes the Jacobi symbol (x / n) in constant time by the same algorithm
+// as jacobi. For applying this method n must be odd and below 2^63, and
+// x < n; this is not checked
+fn ct_jacobi(x: u64, n: u64) -> i32 {
+    // The bit s is 1 if t = -1, and 0 if t = 1, where t is as in jacobi, so
+    // the updates of t are XORs into s
+    let (mut a, mut b, mut s) = (x, n, 0);
+    // The branches of jacobi are replaced by masks, and the loop runs a fixed
+    // number of iterations. The iterations with a = 0 change nothing: a is
+    // even, and the update of s is masked by a > 0
+    for _ in 0..ITERATIONS {
+        let odd = odd_mask(a);
+        let swap = odd & lt_mask(a, b);
+        // a & b & 2 is 2 exactly when a mod 4 = b mod 4 = 3, since both are
+        // odd if swap is set
+        s ^= swap & (a & b) >> 1;
+        (a, b) = (select(swap, b, a), select(swap, a, b));
+        a -= b & odd;
+        // Bits 1 and 2 of b differ exactly when b mod 8 is 3 or 5
+        s ^= nonzero_mask(a) & ((b >> 1) ^ (b >> 2));
+        a >>= 1;
+    }
+    // Now a = 0, so the symbol is (-1)^s if b = 1, and 0 otherwise
+    let one = !nonzero_mask(b ^ 1);
+    ((1 - 2 * (s & 1) as i64) & one as i64) as i32
+}
+
+// Computes the Legendre symbol (x / p) in constant time for an odd prime
+// p < 2^63 and x < p
+fn ct_legendre(x: u64, p: u64) -> i32 {
+    ct_jacobi(x, p)
+}
+
+fn pow_mod(mut x: u64, mut e: u64, n: u64) -> u64 {
+    let mut r = 1 % n;
+    while e > 0 {
+        if e & 1 == 1 { r = (r as u128 * x as u128 % n as u128) as u64; }
+        (x, e) = ((x as u128 * x as u128 % n as u128) as u64, e >> 1);
+    }
+    r
+}
+
+// The Legendre symbol by Euler's criterion for an odd prime p
+fn euler(x: u64, p: u64) -> i32 {
+    match pow_mod(x % p, (p - 1) / 2, p) {
+        0 => 0,
+        1 => 1,
+        _ => -1,
+    }
+}
+
+// The Jacobi symbol by definition: the product of the Legendre symbols for
+// the prime factors of n, which are found by trial division
+fn jacobi_by_factoring(x: u64, mut n: u64) -> i32 {
+    let (mut r, mut p) = (1, 3);
+    while p * p <= n {
+        while n.is_multiple_of(p) { (r, n) = (r * euler(x, p), n / p); }
+        p += 2;
+    }
+    if n > 1 { r * euler(x, n) } else { r }
+}
+
+include!("SplitMix64.rs");
+
+fn main() {
+    // Every pair with a small odd modulus, including n = 1 and the composite
+    // moduli, for which the Jacobi symbol does not decide the residuosity
+    for n in (1..1 << 10).step_by(2) {
+        for x in 0..n {
+            let j = jacobi(x, n);
+            assert!(j == jacobi_by_factoring(x, n), "Incorrect Jacobi symbol ({} / {})!", x, n);
+            assert!(j == ct_jacobi(x, n), "Mismatch with the constant-time version for ({} / {})!", x, n);
+        }
+    }
+    // The Legendre symbols for the Goldilocks prime, which only the
+    // variable-time version supports, and for the Mersenne prime 2^61 - 1
+    let mut state = 2023;
+    for p in [0xffff_ffff_0000_0001, (1 << 61) - 1] {
+        for _ in 0..10_000 {
+            let x = next_u64(&mut state) % p;
+            let l = legendre(x, p);
+            assert!(l == euler(x, p), "Incorrect Legendre symbol ({} / {})!", x, p);
+            assert!(x == 0 || legendre(pow_mod(x, 2, p), p) == 1, "A square is not a square!");
+            if p < 1 << BITS { assert!(l == ct_legendre(x, p), "Mismatch with the constant-time version!"); }
+        }
+        assert!(legendre(0, p) == 0 && legendre(p - 1, p) == euler(p - 1, p), "Incorrect Legendre symbol!");
+    }
+    // Random pairs up to the largest moduli of both versions
+    for k in 0..100_000 {
+        let n = if k < 100 { u64::MAX - 2 * k } else { next_u64(&mut state) | 1 };
+        let x = next_u64(&mut state);
+        let j = jacobi(x, n);
+        assert!(j == jacobi(x % n, n) && j * j <= 1, "Incorrect Jacobi symbol!");
+        let (x, n) = (x >> 1, if k < 100 { (1 << BITS) - 1 - 2 * k } else { n >> 1 | 1 });
+        assert!(jacobi(x, n) == ct_jacobi(x % n, n), "Mismatch with the constant-time version!");
+    }
+    let (x, n) = (5, 97);
+    println!("{}", legendre(x, n));
+}
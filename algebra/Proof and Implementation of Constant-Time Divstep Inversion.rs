@@ -0,0 +1,138 @@
+/*
+  The Constant-Time Modular Inversion by Means of Bernstein-Yang Divsteps:
+                       Implementation in Rust and Proof
+
+                               October 2026
+*/
+// The binary mod_inv branches on the parity of a, on a >= b and on u < 0, so
+// both the sequence of executed branches and the number of iterations depend
+// on x. When x is secret (a private witness value, an ECDSA nonce), this leaks
+// information through timing. The inverter below follows D. J. Bernstein and
+// B.-Y. Yang, "Fast constant-time gcd computation and modular inversion"
+// (2019): every step executes the same instructions, the branches being
+// replaced by masks, and the number of steps is a fixed bound depending only
+// on the bit length of the modulus
+
+// The largest supported modulus is below 2^BITS. With BITS = 62 the sums
+// computed below (such as d + e < 2n and g + f) never overflow an i64
+const BITS: i64 = 62;
+// Theorem 11.2 of the paper: if f is odd, |f| <= 2^b and |g| <= 2^b, then
+// after floor((49b + 57) / 17) divsteps (for b >= 46) starting from delta = 1
+// we have g = 0 and f = +-GCD(f, g)
+const ITERATIONS: i64 = (49 * BITS + 57) / 17;
+
+// Returns -1 (all bits set) if the lowest bit of a is 1, and 0 otherwise
+fn odd_mask(a: i64) -> i64 {
+    -(a & 1)
+}
+
+// Returns -1 if a < b, and 0 otherwise; requires |a - b| < 2^63
+fn lt_mask(a: i64, b: i64) -> i64 {
+    (a - b) >> 63
+}
+
+// Returns a if mask is -1, and b if mask is 0
+fn select(mask: i64, a: i64, b: i64) -> i64 {
+    b ^ (mask & (a ^ b))
+}
+
+// Computes -a mod n for 0 <= a < n without branches
+fn neg_mod(a: i64, n: i64) -> i64 {
+    let t = n - a;
+    t - (n & !lt_mask(t, n))
+}
+
+// Computes a + b mod n for 0 <= a, b < n without branches
+fn add_mod(a: i64, b: i64, n: i64) -> i64 {
+    let t = a + b;
+    t - (n & !lt_mask(t, n))
+}
+
+// Computes a * 2^(-1) mod n for 0 <= a < n and odd n without branches
+fn half_mod(a: i64, n: i64) -> i64 {
+    (a + (n & odd_mask(a))) >> 1
+}
+
+// Computes the multiplicative inverse of x modulo n in constant time by
+// applying Bernstein-Yang divsteps. For applying this method n must be odd
+// and below 2^62, x and n must be coprime, 0 < x < n
+fn ct_mod_inv(x: i64, n: i64) -> i64 {
+    let (mut delta, mut f, mut g, mut d, mut e) = (1, n, x, 0, 1);
+    // Now f = n, g = x;
+    // (1) f is odd; (2) 0 <= d < n and 0 <= e < n; (3) f = d * x (mod n);
+    // (4) g = e * x (mod n); (5) GCD(f, g) = GCD(n, x) = 1 up to sign;
+    // (6) |f| < 2^62 and |g| < 2^62.
+    // Unlike in the binary mod_inv, f and g are signed and there is no
+    // quantity like a + b decreasing in every step. Instead, a divstep maps
+    // (delta, f, g) to (1 - delta, g, (g - f) / 2) if delta > 0 and g is
+    // odd, and to (1 + delta, f, (g + (g mod 2) * f) / 2) otherwise. Each
+    // step preserves (1)-(6), and by Theorem 11.2 after ITERATIONS steps
+    // g = 0, so f = +-1 due to (5) and +-d is the inverse of x modulo n due
+    // to (3)
+    for _ in 0..ITERATIONS {
+        // We swap exactly when delta > 0 and g is odd. Negating delta and
+        // the new g turns the first divstep case into the second one, so
+        // the rest of the step is common for both cases. The swap keeps
+        // (1) since g is odd, keeps (2) since neg_mod maps [0, n) onto
+        // itself, turns (3) and (4) into each other (with -f = (-d) * x)
+        // and does not change GCD(f, g) up to sign, so (1)-(6) hold
+        let swap = lt_mask(0, delta) & odd_mask(g);
+        delta = select(swap, -delta, delta);
+        (f, g) = (select(swap, g, f), select(swap, -f, g));
+        (d, e) = (select(swap, e, d), select(swap, neg_mod(d, n), e));
+        // If g is odd, we add f to g and d to e, which makes g even and keeps
+        // (3)-(5), because GCD(f, g) = GCD(f, g + f). Due to (6), |g + f| <
+        // 2^63 is not an overflow for i64, and add_mod keeps (2)
+        let odd = odd_mask(g);
+        g += f & odd;
+        e = add_mod(e, d & odd, n);
+        // Here g is even. We divide g by 2 and still satisfy (5), since f is
+        // odd due to (1), and (6), since |g| < 2^63 before halving. In order
+        // to satisfy (4), e is set to e * 2^(-1) mod n, which keeps (2)
+        delta += 1;
+        g >>= 1;
+        e = half_mod(e, n);
+    }
+    // Now g = 0 and f = +-1. If f = 1, then d is the inverse of x due to (3);
+    // if f = -1, then it is -d. Since f is odd, its sign is the sign mask of f
+    select(f >> 63, neg_mod(d, n), d)
+}
+
+// The binary mod_inv of "Proof and Implementation of Binary Euclidean
+// Inversion.rs" is the reference; it takes the same moduli below 2^62
+#[allow(dead_code)]
+mod binary {
+    include!("Proof and Implementation of Binary Euclidean Inversion.rs");
+
+    pub fn inv(x: i64, n: i64) -> Result<i64, InversionError> {
+        mod_inv(x, n)
+    }
+}
+
+include!("SplitMix64.rs");
+
+// Checks the inverse of x modulo n, if it exists, i.e. if the binary mod_inv
+// returns it; otherwise ct_mod_inv does not apply
+fn check(x: i64, n: i64) {
+    let Ok(expected) = binary::inv(x, n) else { return };
+    let i = ct_mod_inv(x, n);
+    assert!(i == expected, "Mismatch with the binary mod_inv for {} mod {}!", x, n);
+    assert!((i as i128 * x as i128) % n as i128 == 1, "Incorrect inverse of {} mod {}!", x, n);
+}
+
+fn main() {
+    // Every coprime pair with a small odd modulus
+    for n in (3..1 << 10).step_by(2) {
+        for x in 1..n { check(x, n); }
+    }
+    // Random pairs up to the largest supported modulus 2^62 - 1, including
+    // the moduli right below the bound
+    let mut state = 2023;
+    for k in 0..100_000 {
+        let n = if k < 100 { (1 << BITS) - 1 - 2 * k } else { (next_u64(&mut state) >> 2) as i64 | 1 };
+        let x = (next_u64(&mut state) % n as u64) as i64;
+        check(x, n);
+    }
+    let (x, n) = (13, 97);
+    println!("{}", ct_mod_inv(x, n));
+}
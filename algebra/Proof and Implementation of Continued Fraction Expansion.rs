@@ -0,0 +1,165 @@
+/*
+     The Continued Fraction Expansion of x / n by Means of the Euclidean
+                  Algorithm: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// The classic mod_inv computes the quotients q = b / s and throws them away,
+// but they are the partial quotients of the continued fraction
+// x / n = a_0 + 1 / (a_1 + 1 / (a_2 + ...)), and its cofactors x_s and n_s are,
+// up to sign, the numerators and the denominators of the convergents
+// num_k / den_k = [a_0; a_1, ..., a_k]. The convergents, the remainders and
+// the identity den_k * x - num_k * n = +-r_k are what the lattice analysis of
+// the GLV endomorphism decompositions needs, so the loop below is exposed as
+// an iterator. The values have arbitrary size, since the group orders of BN254
+// and secp256k1 are 254- and 256-bit numbers
+
+// The arbitrary-size integers of Big.rs
+#[allow(dead_code)]
+mod big {
+    include!("Big.rs");
+}
+
+use big::*;
+
+// The k-th step of the expansion of x / n: the partial quotient a_k, the
+// convergent num_k / den_k = [a_0; a_1, ..., a_k] and the remainder
+// r_k = |den_k * x - num_k * n|, where den_k * x - num_k * n has the sign
+// (-1)^k. The last convergent is x / n in lowest terms and has r_k = 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Convergent {
+    q: Big,
+    num: Big,
+    den: Big,
+    rem: Big,
+}
+
+// The iterator over the convergents of x / n for n > 0
+struct Convergents {
+    s: Big,
+    b: Big,
+    num: (Big, Big),
+    den: (Big, Big),
+}
+
+impl Convergents {
+    fn new(x: &Big, n: &Big) -> Convergents {
+        assert!(!n.is_empty(), "The denominator is zero!");
+        // The iterator starts one step before the loop of the classic mod_inv:
+        // its first quotient is a_0 = x / n, after which s = x mod n = x' and
+        // b = n, as in mod_inv_unchecked. Let num_(-2) = 0, den_(-2) = 1,
+        // num_(-1) = 1, den_(-1) = 0, which are stored as (num_(k - 1), num_k)
+        // and (den_(k - 1), den_k) for k = -1, and r_(-2) = x, r_(-1) = n
+        Convergents { s: n.clone(), b: x.clone(), num: (vec![], vec![1]), den: (vec![1], vec![]) }
+    }
+}
+
+impl Iterator for Convergents {
+    type Item = Convergent;
+
+    fn next(&mut self) -> Option<Convergent> {
+        // Before the k-th step s = r_(k - 1) and b = r_(k - 2), and
+        // (1) den_(k - 1) * x - num_(k - 1) * n = (-1)^(k - 1) * s;
+        // (2) den_(k - 2) * x - num_(k - 2) * n = (-1)^(k - 2) * b,
+        // which hold for k = 0 by the definition of the initial values. So the
+        // classic mod_inv has x_s = (-1)^(k - 1) * den_(k - 1) and
+        // n_s = (-1)^k * num_(k - 1), and the same holds for b with k - 2.
+        // With q = b / s, the new convergent num_k = q * num_(k - 1) +
+        // num_(k - 2), den_k = q * den_(k - 1) + den_(k - 2) satisfies
+        // den_k * x - num_k * n = (-1)^(k - 1) * q * s + (-1)^k * b
+        // = (-1)^k * (b - q * s) = (-1)^k * r_k,
+        // so (1) and (2) hold for k + 1. The remainders decrease, so the
+        // expansion ends when s = 0, and the last convergent has r_k = 0, that
+        // is, num_k / den_k = x / n. Also (3) num_k * den_(k - 1) -
+        // num_(k - 1) * den_k = (-1)^(k - 1), since it holds for k = -1, and
+        // each step multiplies the matrix ((num_k, num_(k - 1)), (den_k,
+        // den_(k - 1))) by ((q, 1), (1, 0)) with determinant -1; due to (3),
+        // every convergent is in lowest terms
+        if self.s.is_empty() { return None; }
+        let (q, r) = divrem(&self.b, &self.s);
+        let num = add(&mul(&q, &self.num.1), &self.num.0);
+        let den = add(&mul(&q, &self.den.1), &self.den.0);
+        self.num = (std::mem::take(&mut self.num.1), num.clone());
+        self.den = (std::mem::take(&mut self.den.1), den.clone());
+        self.b = std::mem::replace(&mut self.s, r.clone());
+        Some(Convergent { q, num, den, rem: r })
+    }
+}
+
+// Returns the partial quotients [a_0; a_1, ...] of x / n for n > 0
+fn quotients(x: &Big, n: &Big) -> impl Iterator<Item = Big> {
+    Convergents::new(x, n).map(|c| c.q)
+}
+
+// The quotients of the loop of the classic mod_inv_unchecked from "Proof and
+// Implementation of Euclidean Inversion.rs", which are thrown away there
+fn classic_quotients(x: i64, n: i64) -> Vec<i64> {
+    let (mut s, mut b, mut qs) = (x.rem_euclid(n), n, vec![]);
+    while s > 0 {
+        let q = b / s;
+        (s, b) = (b - q * s, s);
+        qs.push(q);
+    }
+    qs
+}
+
+// Returns the remainder of a modulo n
+fn rem(a: &Big, n: &Big) -> Big {
+    divrem(a, n).1
+}
+
+fn main() {
+    // 415 / 93 = [4; 2, 6, 7]
+    let qs: Vec<Big> = quotients(&vec![415], &vec![93]).collect();
+    assert!(qs == [vec![4], vec![2], vec![6], vec![7]], "Incorrect expansion of 415 / 93!");
+    // Exhaustively for small x and n: after a_0 = x / n, the quotients are
+    // those of the classic loop, the identities of the proof hold, the last
+    // convergent is x / n in lowest terms, and the remainder before it is
+    // GCD(x, n); when it is 1, the previous denominator gives the inverse
+    for n in 1..300u64 {
+        for x in 0..700u64 {
+            let cs: Vec<Convergent> = Convergents::new(&norm(vec![x]), &norm(vec![n])).collect();
+            let qs: Vec<i64> = cs.iter().map(|c| *c.q.first().unwrap_or(&0) as i64).collect();
+            assert!(qs[0] == (x / n) as i64 && qs[1..] == classic_quotients(x as i64, n as i64), "Mismatch with the classic quotients!");
+            let (mut num_prev, mut den_prev) = (1i128, 0i128);
+            for (k, c) in cs.iter().enumerate() {
+                let [num, den, r] = [&c.num, &c.den, &c.rem].map(|v| *v.first().unwrap_or(&0) as i128);
+                let sign = if k % 2 == 0 { 1 } else { -1 };
+                assert!(den * x as i128 - num * n as i128 == sign * r, "Incorrect remainder!");
+                assert!(num * den_prev - num_prev * den == -sign, "Incorrect determinant!");
+                (num_prev, den_prev) = (num, den);
+            }
+            let last = cs.last().unwrap();
+            let gcd = if cs.len() > 1 { cs[cs.len() - 2].rem.clone() } else { norm(vec![n]) };
+            assert!(last.rem.is_empty() && mul(&last.num, &gcd) == norm(vec![x]) && mul(&last.den, &gcd) == vec![n], "Incorrect last convergent!");
+            if gcd == [1] && n > 1 && cs.len() > 1 {
+                let den = cs[cs.len() - 2].den[0] as u128;
+                let r = den * x as u128 % n as u128;
+                assert!(r == 1 || r == n as u128 - 1, "The denominator does not give the inverse!");
+            }
+        }
+    }
+    // The GLV eigenvalues: lambda^2 + lambda + 1 = 0 (mod n). In the
+    // expansion of lambda / n the remainders decrease from n and the
+    // denominators grow from 1, and den_k * r_(k - 1) + den_(k - 1) * r_k = n
+    // (by induction, as (3)). For the first k with r_k < 2^(h / 2), where
+    // h = bits(n), r_(k - 1) >= 2^(h / 2), so den_k <= n / r_(k - 1) has at
+    // most h - h / 2 bits: (r_k, -+den_k) is a short vector of the lattice of
+    // (k_1, k_2) with k_1 + k_2 * lambda = 0 (mod n)
+    for (name, n, lambda) in [("BN254", BN254_R, BN254_LAMBDA), ("secp256k1", SECP256K1_N, SECP256K1_LAMBDA)] {
+        let (n, lambda) = (from_hex(n), from_hex(lambda));
+        let sum = add(&add(&mul(&lambda, &lambda), &lambda), &vec![1]);
+        assert!(rem(&sum, &n).is_empty(), "Lambda is not a cube root of unity!");
+        let cs: Vec<Convergent> = Convergents::new(&lambda, &n).collect();
+        let (mut rem_prev, mut den_prev) = (n.clone(), vec![]);
+        for c in &cs {
+            assert!(add(&mul(&c.den, &rem_prev), &mul(&den_prev, &c.rem)) == n, "Incorrect identity!");
+            (rem_prev, den_prev) = (c.rem.clone(), c.den.clone());
+        }
+        let k = cs.iter().position(|c| bits(&c.rem) <= bits(&n) / 2).unwrap();
+        let (den, r) = (&cs[k].den, &cs[k].rem);
+        assert!(bits(den) <= bits(&n) - bits(&n) / 2, "The vector is not short!");
+        assert!(rem(&mul(den, &lambda), &n) == *r || rem(&add(&mul(den, &lambda), r), &n).is_empty(), "Not a lattice vector!");
+        println!("{}: {} quotients, r_{} has {} bits, den_{} has {} bits", name, cs.len(), k, bits(r), k, bits(den));
+    }
+}
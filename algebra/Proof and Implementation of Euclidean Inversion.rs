@@ -5,33 +5,264 @@
                             Aleksei Vambol
                               June 2023
 */
+include!("InversionError.rs");
+
 // Computes the multiplicative inverse of x modulo n by applying the Extended 
-// Euclidean Algorithm; panics in the case of n < 2. If x and n are not coprime, 
-// the aforementioned inverse does not exist, so None is returned
-fn mod_inv(x: i64, n: i64) -> Option<i64> {
-    if n < 2 { panic!("The modulus must be greater than 1!"); }
+// Euclidean Algorithm. If n < 2, or x and n are not coprime, the reason why 
+// the inverse does not exist is returned instead
+fn mod_inv(x: i64, n: i64) -> Result<i64, InversionError> {
+    if n < 2 { return Err(InversionError::ModulusTooSmall); }
+    let r = mod_inv_unchecked(x, n);
+    // After euclid_loop in mod_inv_unchecked b = GCD(x', n) = GCD(x, n) and 
+    // b = x_b * x' + n_b * n, so b = r * x (mod n). Since 0 < b <= n, the 
+    // remainder of r * x modulo n is GCD(x, n), or 0, if GCD(x, n) = n
+    let gcd = match (r as i128 * x as i128).rem_euclid(n as i128) as i64 {
+        0 => n,
+        b => b,
+    };
+    if gcd == 1 { Ok(r) } else { Err(InversionError::NotCoprime { gcd }) }
+}
+
+// Computes the multiplicative inverse of x modulo n by applying the Extended 
+// Euclidean Algorithm; n must be greater than 1 and x and n must be coprime. 
+// None of this is checked: if x and n are not coprime, the returned value is 
+// not the inverse, and n < 2 results in a division by zero or a wrong value
+fn mod_inv_unchecked(x: i64, n: i64) -> i64 {
     // Working not with x, but with such x' that 0 <= x' < n and x = x' (mod n)
-    let (mut s, mut x_s, mut b, mut x_b) = (((x % n) + n) % n, 1, n, 0);
+    let (_, x_b, _) = euclid_loop(x.rem_euclid(n), n);
+    // After the loop b = GCD(0, b) = GCD(x', n). If b > 1, then x' is  
+    // not invertible modulo n. If b = 1, then 1 = x_b * x' + n_b * n, 
+    // so x_b * x' = 1 (mod n); since it is proven that |x_b| does not 
+    // exceed n, we return either x_b or x_b + n
+    if x_b < 0 { x_b + n } else { x_b }
+}
+
+// Runs the loop of the Extended Euclidean Algorithm for 0 <= x' < n and
+// returns the final b and x_b together with the number of iterations
+fn euclid_loop(x: i64, n: i64) -> (i64, i64, u32) {
+    let (mut s, mut x_s, mut b, mut x_b, mut iterations) = (x, 1, n, 0, 0);
     // Now s = x', b = n; "s" and "b" stand for "small" and "big", respectively.
     // From now on we have s = x_s * x' + n_s * n and b = x_b * x' + n_b * n,
     // where x' and n are immutable. In each iteration until s = 0 we use   
     // the formula "GCD(s, b) = GCD(b mod s, s)" and update the variables 
-    // accordingly. We do not need to store the values of n_s and n_b
+    // accordingly. We do not need to store the values of n_s and n_b, but
+    // in the proof mode they are stored, and the invariants (1)-(5) of
+    // ext_gcd, which hold for x' as well, are checked in every iteration.
+    // By Lame's theorem, if there are k iterations for 0 < x' < n, then
+    // x' >= F_(k + 1) and n >= F_(k + 2) for the Fibonacci numbers
+    // F_1 = F_2 = 1. Let r_0 = n, r_1 = x', ..., r_k be the non-zero
+    // remainders and r_(k + 1) = 0; then r_i >= F_(k + 2 - i): r_k >= 1 = F_2,
+    // r_(k - 1) > r_k gives r_(k - 1) >= 2 = F_3, and r_(i - 1) =
+    // q_i * r_i + r_(i + 1) >= r_i + r_(i + 1) >= F_(k + 3 - i)
+    #[cfg(feature = "proof")]
+    let (mut n_s, mut n_b, mut q_last) = (0, 1, 0);
     while s > 0 {
+        #[cfg(feature = "proof")]
+        check_invariants((x, n), (s, x_s, n_s), (b, x_b, n_b), q_last);
         let q = b / s;
         (s, x_s, b, x_b) = (b - q * s, x_b - q * x_s, s, x_s);
+        #[cfg(feature = "proof")]
+        { (n_s, n_b, q_last) = (n_b - q * n_s, n_s, q); }
+        iterations += 1;
     }
-    // Now we have b = GCD(0, b) = GCD(x', n). If b > 1, then x' is  
-    // not invertible modulo n. If b = 1, then 1 = x_b * x' + n_b * n, 
-    // so x_b * x' = 1 (mod n); since it is proven that |x_b| does not 
-    // exceed n, we return either x_b or x_b + n
-    if b == 1 { Some(if x_b < 0 { x_b + n } else { x_b }) } else { None }
+    #[cfg(feature = "proof")]
+    check_invariants((x, n), (s, x_s, n_s), (b, x_b, n_b), q_last);
+    (b, x_b, iterations)
+}
+
+// Asserts the invariants (1)-(5) of ext_gcd for its inputs x and n, the
+// rows (s, x_s, n_s) and (b, x_b, n_b), and the last quotient q_last, which
+// is 0 before the first iteration, as well as GCD(s, b) = GCD(x, n) and
+// 0 <= s < b after the first iteration. Compiled only in the proof mode,
+// which is enabled by --cfg 'feature="proof"'
+#[cfg(feature = "proof")]
+fn check_invariants((x, n): (i64, i64), (s, x_s, n_s): (i64, i64, i64), (b, x_b, n_b): (i64, i64, i64), q_last: i64) {
+    let gcd = |mut p: i64, mut q: i64| {
+        while q > 0 { (p, q) = (q, p % q); }
+        p
+    };
+    let combine = |c_x: i64, c_n: i64| c_x as i128 * x as i128 + c_n as i128 * n as i128;
+    assert!(s as i128 == combine(x_s, n_s), "Invariant (1) is broken!");
+    assert!(b as i128 == combine(x_b, n_b), "Invariant (2) is broken!");
+    assert!((x_b as i128 * n_s as i128 - x_s as i128 * n_b as i128).abs() == 1, "Invariant (3) is broken!");
+    assert!(x_s.signum() * x_b.signum() <= 0 && n_s.signum() * n_b.signum() <= 0, "Invariant (4) is broken!");
+    assert!(q_last == 0 || (x_b.abs() <= x_s.abs() && n_b.abs() <= n_s.abs()), "Invariant (5) is broken!");
+    assert!(gcd(s, b) == gcd(x, n), "The GCD is not preserved!");
+    assert!(q_last == 0 || (0 <= s && s < b), "The remainders do not decrease!");
+}
+
+// Computes y * x^(-1) mod n by applying the Extended Euclidean Algorithm. If
+// n < 2, or x and n are not coprime, the reason why the quotient does not
+// exist is returned instead
+fn mod_div(y: i64, x: i64, n: i64) -> Result<i64, InversionError> {
+    if n < 2 { return Err(InversionError::ModulusTooSmall); }
+    let (mut s, mut x_s, mut b, mut x_b) = (x.rem_euclid(n), y.rem_euclid(n) as i128, n, 0i128);
+    // This is euclid_loop, where x_s starts with y' = y mod n instead of 1.
+    // Since the coefficients are transformed linearly, x_s and x_b are y'
+    // times the ones of euclid_loop, so
+    // (1) s * y' = x_s * x' (mod n); (2) b * y' = x_b * x' (mod n),
+    // which hold true now, since b = n = 0 (mod n). When b = 1 after the
+    // loop, x_b * x' = y' (mod n). The coefficients of euclid_loop do not
+    // exceed n in absolute value, so here |x_s|, |x_b| <= y' * n < 2^126,
+    // and q * |x_s| <= |x_b - q * x_s| (the signs alternate) fits as well.
+    // Thus, the coefficients are kept unreduced in i128, and x_b is reduced
+    // modulo n only once after the loop
+    while s > 0 {
+        let q = b / s;
+        (s, x_s, b, x_b) = (b - q * s, x_b - q as i128 * x_s, s, x_s);
+    }
+    if b == 1 { Ok(x_b.rem_euclid(n as i128) as i64) } else { Err(InversionError::NotCoprime { gcd: b }) }
+}
+
+// Computes g = GCD(x, n) together with the Bezout coefficients c_x and c_n, 
+// such that g = c_x * x + c_n * n, by applying the Extended Euclidean 
+// Algorithm to non-negative x and positive n. If x > 0, then |c_x| <= n / g 
+// and |c_n| <= x / g; if x = 0, then (g, c_x, c_n) = (n, 0, 1)
+fn ext_gcd(x: i64, n: i64) -> (i64, i64, i64) {
+    let (mut s, mut x_s, mut n_s, mut b, mut x_b, mut n_b) = (x, 1, 0, n, 0, 1);
+    // Now s = x, b = n and, unlike in mod_inv_unchecked, x is not reduced and 
+    // the coefficients of n are stored. We have 
+    // (1) s = x_s * x + n_s * n; (2) b = x_b * x + n_b * n; 
+    // (3) x_b * n_s - x_s * n_b = 1 or -1; 
+    // (4) x_s and x_b have opposite signs or one of them is 0, and so do 
+    // n_s and n_b; (5) |x_b| <= |x_s| and |n_b| <= |n_s| after each 
+    // iteration with q > 0. In each iteration the pair of rows (x_s, n_s) and 
+    // (x_b, n_b) is multiplied by the matrix ((-q, 1), (1, 0)), whose 
+    // determinant is -1, so (3) is preserved. Due to (4) the new x_s is 
+    // x_b - q * x_s, whose absolute value is |x_b| + q * |x_s| >= |x_s| 
+    // for q > 0 (q = 0 only in the first iteration if x < n, which just 
+    // swaps s and b), and its sign is the sign of x_b, which preserves (4) 
+    // and (5); the same is true for the coefficients of n. (1) and (2) are 
+    // preserved as in euclid_loop
+    #[cfg(feature = "proof")]
+    let mut q_last = 0;
+    while s > 0 {
+        #[cfg(feature = "proof")]
+        check_invariants((x, n), (s, x_s, n_s), (b, x_b, n_b), q_last);
+        let q = b / s;
+        (s, x_s, n_s, b, x_b, n_b) = (b - q * s, x_b - q * x_s, n_b - q * n_s, s, x_s, n_s);
+        #[cfg(feature = "proof")]
+        { q_last = q; }
+    }
+    #[cfg(feature = "proof")]
+    check_invariants((x, n), (s, x_s, n_s), (b, x_b, n_b), q_last);
+    // Now b = GCD(0, b) = GCD(x, n) = g and 0 = x_s * x + n_s * n due to (1). 
+    // Due to (3), GCD(x_s, n_s) = 1, since it divides x_b * n_s - x_s * n_b. 
+    // Dividing by g gives x_s * (x / g) = -n_s * (n / g), where x / g and 
+    // n / g are coprime, so n / g divides x_s and x / g divides n_s. If x > 0, 
+    // then x_s is not 0 (otherwise n_s * n = 0 and n_s = 0, which breaks (3)), 
+    // and n_s is not 0 as well, so x_s = c * n / g and n_s = -c * x / g for 
+    // some integer c, which divides GCD(x_s, n_s) = 1. Thus, |x_s| = n / g 
+    // and |n_s| = x / g. The last iteration has q > 0, since s <= b in each 
+    // iteration but the first, so by (5) |x_b| <= n / g and |n_b| <= x / g. 
+    // The absolute values of all the coefficients computed in the loop do 
+    // not exceed the final ones, so the loop does not overflow
+    (b, x_b, n_b)
+}
+
+// Finds the fraction num / den = r (mod n) with |num| <= N and 0 < den <= N
+// for N = floor(sqrt((n - 1) / 2)) by stopping euclid_loop early. Such a
+// fraction is unique, and None is returned if it does not exist or if n < 2.
+// The fraction is in lowest terms, and den is coprime with n
+fn rational_reconstruct(r: i64, n: i64) -> Option<(i64, i64)> {
+    if n < 2 { return None; }
+    let bound = ((n - 1) / 2).isqrt();
+    let (mut s, mut x_s, mut b, mut x_b) = (r.rem_euclid(n), 1, n, 0);
+    // This is euclid_loop, so s = x_s * r (mod n) holds true for every
+    // remainder s, i.e. r = s / x_s whenever x_s is coprime with n. It stops
+    // at the first remainder s <= N instead of s = 0.
+    // If r = a / b with |a| <= N and 0 < b <= N, then 2 * |a| * b < n, and
+    // the theorem of Wang (see also von zur Gathen and Gerhard, "Modern
+    // Computer Algebra", Theorem 5.26) states that (a, b) = (s, x_s) up to
+    // sign for this very remainder. Thus, if (s, x_s) fails the checks below,
+    // there is no such fraction, and if it passes them, it is the fraction
+    while s > bound {
+        let q = b / s;
+        (s, x_s, b, x_b) = (b - q * s, x_b - q * x_s, s, x_s);
+    }
+    let (num, den) = if x_s < 0 { (-s, -x_s) } else { (s, x_s) };
+    let coprime = |mut a: i64, mut b: i64| {
+        while b > 0 { (a, b) = (b, a % b); }
+        a == 1
+    };
+    if den <= bound && coprime(num.abs(), den) && coprime(den, n) { Some((num, den)) } else { None }
 }
 
 fn main() {
+    if cfg!(feature = "proof") { println!("Proof mode: the invariants are checked in every iteration"); }
     let (x, n) = (3, 10);
     match mod_inv(x, n) {
-        Some(r) => println!("{}", r),
-        None => println!("{} and {} are not coprime!", x, n),
+        Ok(r) => println!("{}", r),
+        Err(InversionError::NotCoprime { gcd }) => println!("GCD({}, {}) = {}!", x, n, gcd),
+        Err(InversionError::ModulusTooSmall) => println!("The modulus must be greater than 1!"),
+        Err(e) => unreachable!("The classic algorithm does not return {:?}!", e),
+    }
+    assert!(mod_inv_unchecked(x, n) == 7, "Incorrect inverse!");
+    assert!(mod_inv(-x, n) == Ok(3), "Incorrect inverse!");
+    assert!(mod_inv(x, 1) == Err(InversionError::ModulusTooSmall), "Incorrect error!");
+    assert!(mod_inv(6, 15) == Err(InversionError::NotCoprime { gcd: 3 }), "Incorrect error!");
+    assert!(mod_inv(-30, 15) == Err(InversionError::NotCoprime { gcd: 15 }), "Incorrect error!");
+    // The moduli near i64::MAX, where x % n + n would overflow
+    let n = i64::MAX;
+    assert!(mod_inv(n - 1, n) == Ok(n - 1) && mod_inv(-1, n) == Ok(n - 1), "Incorrect inverse!");
+    assert!(mod_inv(i64::MIN, n) == Ok(n - 1) && mod_inv(2, n) == Ok(n / 2 + 1), "Incorrect inverse!");
+    assert!(mod_inv(n - 7, n) == Err(InversionError::NotCoprime { gcd: 7 }), "Incorrect error!");
+    // The division agrees with the multiplication by the inverse
+    for n in 1..300i64 {
+        for x in -300..300i64 {
+            for y in [-n - 1, -1, 0, 1, 2, x, n - 1, 1000] {
+                let expected = mod_inv(x, n).map(|i| (y.rem_euclid(n) * i) % n);
+                assert!(mod_div(y, x, n) == expected, "Incorrect quotient!");
+            }
+        }
     }
-}
\ No newline at end of file
+    assert!(mod_div(5, 6, 15) == Err(InversionError::NotCoprime { gcd: 3 }), "Incorrect error!");
+    let (y, x, n) = (i64::MIN, i64::MAX - 1, i64::MAX);
+    let q = mod_div(y, x, n).unwrap();
+    assert!((q as i128 * x as i128 - y as i128) % n as i128 == 0, "Incorrect quotient!");
+    // Lame's bound on the number of iterations for all small pairs
+    let fib: Vec<i64> = (0..20).scan((0, 1), |f, _| { let r = f.0; *f = (f.1, f.0 + f.1); Some(r) }).collect();
+    for n in 2..300i64 {
+        for x in 1..n {
+            let k = euclid_loop(x, n).2 as usize;
+            assert!(x >= fib[k + 1] && n >= fib[k + 2], "Too many iterations!");
+        }
+    }
+    // The Bezout coefficients and their bounds for all small pairs
+    for n in 1..300i64 {
+        for x in 0..300i64 {
+            let (g, c_x, c_n) = ext_gcd(x, n);
+            assert!(n % g == 0 && x % g == 0 && g == c_x * x + c_n * n, "Incorrect GCD!");
+            if x > 0 {
+                assert!(c_x.abs() <= n / g && c_n.abs() <= x / g, "Incorrect bounds!");
+            } else {
+                assert!((g, c_x, c_n) == (n, 0, 1), "Incorrect GCD!");
+            }
+        }
+    }
+    let (x, n) = (i64::MAX - 1, i64::MAX);
+    assert!(ext_gcd(x, n) == (1, -1, 1), "Incorrect GCD!");
+    assert!(ext_gcd(n, x) == (1, 1, -1), "Incorrect GCD!");
+    // Every fraction within the bounds is recovered, and every result is a
+    // fraction within the bounds
+    for n in [1009i64, 1024, 3 * 5 * 7 * 11] {
+        let bound = ((n - 1) / 2).isqrt();
+        for num in -bound..=bound {
+            for den in (1..=bound).filter(|&d| ext_gcd(num.abs(), d).0 == 1 && ext_gcd(d, n).0 == 1) {
+                let r = (num * mod_inv_unchecked(den, n)).rem_euclid(n);
+                assert!(rational_reconstruct(r, n) == Some((num, den)), "Incorrect fraction!");
+            }
+        }
+        for r in 0..n {
+            if let Some((num, den)) = rational_reconstruct(r, n) {
+                assert!(num.abs() <= bound && 0 < den && den <= bound, "Incorrect bounds!");
+                assert!((num - r * den).rem_euclid(n) == 0, "Incorrect fraction!");
+            }
+        }
+    }
+    let n = (1 << 61) - 1;
+    let r = (-123456791 * mod_inv_unchecked(987654323, n) as i128).rem_euclid(n as i128) as i64;
+    assert!(rational_reconstruct(r, n) == Some((-123456791, 987654323)), "Incorrect fraction!");
+    assert!(rational_reconstruct(0, n) == Some((0, 1)) && rational_reconstruct(5, 1).is_none(), "Incorrect fraction!");
+    assert!(rational_reconstruct(n / 2, n) == Some((-1, 2)), "Incorrect fraction!");
+}
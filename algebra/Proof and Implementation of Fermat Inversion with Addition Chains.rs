@@ -0,0 +1,438 @@
+/*
+  The Modular Inversion in Prime Fields by Means of Fermat's Little Theorem
+           and Addition Chains: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// For a prime p and x != 0 (mod p) Fermat's little theorem gives
+// x^(p - 1) = 1 (mod p), so x^(-1) = x^(p - 2). The exponent is public, so
+// the sequence of multiplications and squarings depends only on p, and with
+// a branch-free multiplication the inversion is constant-time by
+// construction, unlike the Euclidean mod_inv algorithms. The cost is the
+// length of the addition chain for p - 2: about log(p) squarings, which
+// cannot be avoided, plus the multiplications, which a good chain reduces.
+// The chains for secp256k1, BN254 and BLS12-381 are written out; for an
+// arbitrary prime the generator derives a chain by the sliding window method
+
+// A 384-bit unsigned integer stored as six 64-bit limbs, the least
+// significant limb first. This is enough for the BLS12-381 base field
+// modulus (381 bits) and for all the smaller moduli
+type U384 = [u64; 6];
+
+const ZERO: U384 = [0; 6];
+const ONE: U384 = [1, 0, 0, 0, 0, 0];
+
+// The BN254 base field modulus q and scalar field modulus r
+const BN254_Q: U384 = [0x3c208c16d87cfd47, 0x97816a916871ca8d, 0xb85045b68181585d, 0x30644e72e131a029, 0, 0];
+const BN254_R: U384 = [0x43e1f593f0000001, 0x2833e84879b97091, 0xb85045b68181585d, 0x30644e72e131a029, 0, 0];
+// The BLS12-381 base field modulus p and scalar field modulus r
+const BLS12_381_P: U384 = [
+    0xb9feffffffffaaab, 0x1eabfffeb153ffff, 0x6730d2a0f6b0f624,
+    0x64774b84f38512bf, 0x4b1ba7b6434bacd7, 0x1a0111ea397fe69a,
+];
+const BLS12_381_R: U384 = [0xffffffff00000001, 0x53bda402fffe5bfe, 0x3339d80809a1d805, 0x73eda753299d7d48, 0, 0];
+// The secp256k1 base field modulus 2^256 - 2^32 - 977
+const SECP256K1_P: U384 = [0xfffffffefffffc2f, u64::MAX, u64::MAX, u64::MAX, 0, 0];
+
+// The arithmetic and the binary mod_inv of "Proof and Implementation of
+// Multi-Limb Binary Euclidean Inversion.rs" for six limbs; the constant-time
+// add_mod and mul_mod below replace the ones of the module
+#[allow(dead_code)]
+mod limbs {
+    include!("MultiLimb.rs");
+}
+
+use limbs::*;
+
+// A step of an addition chain. Each step computes a new value from the
+// previous ones: Mul(i, j) is t_i * t_j and Sqr(i, k) is t_i^(2^k), which
+// takes k squarings
+enum Op {
+    Mul(usize, usize),
+    Sqr(usize, u32),
+}
+
+// The input x is the value t_0
+const X: usize = 0;
+
+// An addition chain: the value t_i for i > 0 is computed by ops[i - 1], and
+// the result is the last value
+struct Chain {
+    ops: Vec<Op>,
+}
+
+impl Chain {
+    fn new() -> Chain {
+        Chain { ops: vec![] }
+    }
+
+    // Appends t_i * t_j and returns its index
+    fn mul(&mut self, i: usize, j: usize) -> usize {
+        self.ops.push(Op::Mul(i, j));
+        self.ops.len()
+    }
+
+    // Appends t_i^(2^k) and returns its index
+    fn sqr(&mut self, i: usize, k: u32) -> usize {
+        self.ops.push(Op::Sqr(i, k));
+        self.ops.len()
+    }
+
+    // Returns the numbers of multiplications and squarings
+    fn cost(&self) -> (u32, u32) {
+        self.ops.iter().fold((0, 0), |(m, s), op| match op {
+            Op::Mul(..) => (m + 1, s),
+            Op::Sqr(_, k) => (m, s + k),
+        })
+    }
+
+    // Returns the exponent e, such that the chain computes x^e, by running
+    // it on the exponents: t_i * t_j adds them, and t_i^(2^k) multiplies by
+    // 2^k. The exponent must be below 2^384
+    fn exponent(&self) -> U384 {
+        let mut e = vec![ONE];
+        for op in &self.ops {
+            e.push(match *op {
+                Op::Mul(i, j) => add(&e[i], &e[j]).0,
+                Op::Sqr(i, k) => (0..k).fold(e[i], |a, _| add(&a, &a).0),
+            });
+        }
+        *e.last().unwrap()
+    }
+
+    // Computes x^e mod p for x < p, where e is the exponent of the chain
+    fn pow(&self, x: &U384, p: &U384) -> U384 {
+        let mut t = vec![*x];
+        for op in &self.ops {
+            t.push(match *op {
+                Op::Mul(i, j) => mul_mod(&t[i], &t[j], p),
+                Op::Sqr(i, k) => (0..k).fold(t[i], |a, _| mul_mod(&a, &a, p)),
+            });
+        }
+        *t.last().unwrap()
+    }
+}
+
+// The chain for a window decomposition (d_0, [(s_1, d_1), ..., (s_k, d_k)]),
+// which stands for (...((d_0 * 2^s_1 + d_1) * 2^s_2 + d_2) ...) * 2^s_k + d_k
+// with odd digits d_i; d_i = 0 stands for no multiplication, which is needed
+// for trailing zero bits. The table of x, x^3, x^5, ..., x^D for the largest digit D
+// costs one squaring and (D - 1) / 2 multiplications
+fn window_chain(first: u32, steps: &[(u32, u32)]) -> Chain {
+    let mut c = Chain::new();
+    let max_digit = steps.iter().map(|&(_, d)| d).fold(first, u32::max);
+    // The pairs (d, index of x^d) for d = 1, 3, ..., D
+    let mut table = vec![(1, X)];
+    if max_digit > 1 {
+        let x2 = c.sqr(X, 1);
+        for d in (3..=max_digit).step_by(2) { table.push((d, c.mul(table.last().unwrap().1, x2))); }
+    }
+    append_windows(&mut c, &table, first, steps);
+    c
+}
+
+// Appends the squarings and multiplications of a window decomposition
+// (d_0, [(s_1, d_1), ..., (s_k, d_k)]) to c, taking x^d from the table of
+// the pairs (d, index of x^d), which must contain all the digits, and
+// returns the index of the result
+fn append_windows(c: &mut Chain, table: &[(u32, usize)], first: u32, steps: &[(u32, u32)]) -> usize {
+    let index = |d: u32| table.iter().find(|&&(e, _)| e == d).expect("The digit is not in the table!").1;
+    let mut acc = index(first);
+    for &(s, d) in steps {
+        acc = c.sqr(acc, s);
+        if d > 0 { acc = c.mul(acc, index(d)); }
+    }
+    acc
+}
+
+// The inversion chain for secp256k1 from libsecp256k1. Here p - 2 consists
+// of 223 ones, a zero, 22 ones and then the bits 0000101101. Let x_k be
+// x^(2^k - 1), i.e. x to the power of k ones; then x_(i + j) =
+// x_i^(2^j) * x_j, so the blocks of ones are built by doubling, and the long
+// block at the top becomes the accumulator. The chain takes 255 squarings and
+// 15 multiplications, while the sliding windows need 66 multiplications
+fn secp256k1_chain() -> Chain {
+    let mut c = Chain::new();
+    let t = c.sqr(X, 1);
+    let x2 = c.mul(t, X);
+    let t = c.sqr(x2, 1);
+    let x3 = c.mul(t, X);
+    let t = c.sqr(x3, 3);
+    let x6 = c.mul(t, x3);
+    let t = c.sqr(x6, 3);
+    let x9 = c.mul(t, x3);
+    let t = c.sqr(x9, 2);
+    let x11 = c.mul(t, x2);
+    let t = c.sqr(x11, 11);
+    let x22 = c.mul(t, x11);
+    let t = c.sqr(x22, 22);
+    let x44 = c.mul(t, x22);
+    let t = c.sqr(x44, 44);
+    let x88 = c.mul(t, x44);
+    let t = c.sqr(x88, 88);
+    let x176 = c.mul(t, x88);
+    let t = c.sqr(x176, 44);
+    let x220 = c.mul(t, x44);
+    let t = c.sqr(x220, 3);
+    let x223 = c.mul(t, x3);
+    // 223 ones, then 0 and 22 ones, then 0000 and 1, 0 and 11, 0 and 1
+    let t = c.sqr(x223, 23);
+    let t = c.mul(t, x22);
+    let t = c.sqr(t, 5);
+    let t = c.mul(t, X);
+    let t = c.sqr(t, 3);
+    let t = c.mul(t, x2);
+    let t = c.sqr(t, 2);
+    c.mul(t, X);
+    c
+}
+
+// The chains for BN254 and BLS12-381, whose p - 2 have no long blocks of
+// ones, were found by a search in the style of addchain: the decomposition of
+// p - 2 into windows over a given set of odd digits is the one with the
+// fewest windows, found by dynamic programming, and the set of digits is
+// chosen by simulated annealing. Unlike in the sliding window method, the
+// digits are not all the odd values below the largest one: the table is an
+// addition sequence, where each digit is the sum of two earlier values or
+// a * 2^k + b for earlier a and b, so a few large digits cost little. Each
+// function builds the table, where x_d stands for x^d, and appends the
+// windows; main checks that the chains compute p - 2
+// BN254 q: 51 multiplications and 251 squarings, where the generated chain
+// takes 53 and 253
+fn bn254_q_chain() -> Chain {
+    let mut c = Chain::new();
+    let x_2 = c.sqr(X, 1);
+    let x_3 = c.mul(X, x_2);
+    let x_5 = c.mul(x_2, x_3);
+    let x_7 = c.mul(x_2, x_5);
+    let x_9 = c.mul(x_2, x_7);
+    let x_11 = c.mul(x_2, x_9);
+    let x_13 = c.mul(x_2, x_11);
+    let x_15 = c.mul(x_2, x_13);
+    let t = c.sqr(x_2, 1);
+    let x_19 = c.mul(t, x_15);
+    let t = c.sqr(x_13, 1);
+    let x_45 = c.mul(t, x_19);
+    let t = c.sqr(x_45, 2);
+    let x_193 = c.mul(t, x_13);
+    let table = [
+        (1, X), (3, x_3), (5, x_5), (7, x_7), (9, x_9), (11, x_11), (13, x_13), (15, x_15),
+        (19, x_19), (45, x_45), (193, x_193),
+    ];
+    let steps = [
+        (4, 9), (8, 19), (5, 19), (4, 9), (4, 7), (9, 19), (7, 13), (8, 1), (6, 19), (4, 7), (7, 5),
+        (6, 1), (9, 45), (6, 45), (8, 3), (7, 1), (5, 11), (7, 5), (4, 13), (4, 9), (5, 15),
+        (10, 11), (4, 5), (5, 9), (9, 45), (7, 7), (5, 3), (4, 9), (4, 5), (7, 13), (6, 15), (5, 1),
+        (6, 1), (5, 3), (11, 45), (4, 11), (8, 15), (5, 19), (4, 15), (4, 5), (6, 5),
+    ];
+    append_windows(&mut c, &table, 193, &steps);
+    c
+}
+
+// BN254 r: 52 multiplications and 251 squarings, where the generated chain
+// takes 56 and 253
+fn bn254_r_chain() -> Chain {
+    let mut c = Chain::new();
+    let x_2 = c.sqr(X, 1);
+    let x_3 = c.mul(X, x_2);
+    let x_5 = c.mul(x_2, x_3);
+    let x_7 = c.mul(x_2, x_5);
+    let x_9 = c.mul(x_2, x_7);
+    let x_11 = c.mul(x_2, x_9);
+    let x_13 = c.mul(x_2, x_11);
+    let t = c.sqr(x_9, 1);
+    let x_31 = c.mul(t, x_13);
+    let x_33 = c.mul(x_2, x_31);
+    let t = c.sqr(x_31, 1);
+    let x_69 = c.mul(t, x_7);
+    let t = c.sqr(x_31, 2);
+    let x_193 = c.mul(t, x_69);
+    let table = [
+        (1, X), (3, x_3), (5, x_5), (7, x_7), (9, x_9), (11, x_11), (13, x_13), (31, x_31),
+        (33, x_33), (69, x_69), (193, x_193),
+    ];
+    let steps = [
+        (4, 9), (4, 1), (5, 7), (5, 7), (6, 11), (6, 33), (4, 3), (7, 13), (10, 5), (4, 3), (4, 7),
+        (7, 5), (12, 69), (4, 11), (5, 13), (8, 3), (7, 1), (5, 11), (7, 5), (4, 13), (5, 5),
+        (7, 3), (7, 31), (7, 33), (8, 33), (3, 7), (4, 3), (4, 7), (6, 11), (6, 33), (9, 69),
+        (9, 31), (9, 31), (4, 5), (4, 9), (7, 31), (4, 7), (5, 31), (5, 31), (5, 31), (5, 31),
+        (5, 31),
+    ];
+    append_windows(&mut c, &table, 193, &steps);
+    c
+}
+
+// BLS12-381 p: 70 multiplications and 382 squarings, where the generated
+// chain takes 82 and 378
+fn bls12_381_p_chain() -> Chain {
+    let mut c = Chain::new();
+    let x_2 = c.sqr(X, 1);
+    let x_3 = c.mul(X, x_2);
+    let x_5 = c.mul(x_2, x_3);
+    let x_7 = c.mul(x_2, x_5);
+    let x_9 = c.mul(x_2, x_7);
+    let x_11 = c.mul(x_2, x_9);
+    let x_13 = c.mul(x_2, x_11);
+    let t = c.sqr(x_5, 1);
+    let x_21 = c.mul(t, x_11);
+    let x_23 = c.mul(x_2, x_21);
+    let t = c.sqr(x_23, 1);
+    let x_59 = c.mul(t, x_13);
+    let t = c.sqr(x_59, 1);
+    let x_123 = c.mul(t, x_5);
+    let t = c.sqr(x_123, 1);
+    let x_255 = c.mul(t, x_9);
+    let table = [
+        (1, X), (3, x_3), (5, x_5), (7, x_7), (9, x_9), (11, x_11), (13, x_13), (21, x_21),
+        (23, x_23), (59, x_59), (123, x_123), (255, x_255),
+    ];
+    let steps = [
+        (9, 1), (4, 1), (6, 7), (5, 21), (6, 7), (6, 11), (8, 255), (6, 13), (4, 3), (5, 9),
+        (6, 11), (7, 13), (4, 13), (9, 123), (2, 1), (4, 9), (8, 13), (7, 23), (5, 11), (6, 13),
+        (7, 59), (3, 1), (9, 59), (3, 5), (7, 23), (8, 9), (3, 7), (5, 7), (7, 5), (7, 9), (5, 11),
+        (7, 123), (5, 7), (4, 3), (8, 13), (7, 21), (12, 123), (5, 11), (11, 123), (7, 9), (7, 3),
+        (4, 13), (6, 21), (8, 255), (8, 255), (5, 11), (8, 21), (4, 3), (8, 255), (8, 255), (5, 23),
+        (10, 255), (9, 255), (8, 255), (8, 255), (8, 255), (5, 21), (6, 21), (5, 9),
+    ];
+    append_windows(&mut c, &table, 13, &steps);
+    c
+}
+
+// BLS12-381 r: 47 multiplications and 255 squarings, where the generated
+// chain takes 59 and 253. The runs of ones at the bottom, which come from
+// r - 2 = ...fffffffeffffffff, take one multiplication by x_255 per 8 ones
+fn bls12_381_r_chain() -> Chain {
+    let mut c = Chain::new();
+    let x_2 = c.sqr(X, 1);
+    let x_3 = c.mul(X, x_2);
+    let x_5 = c.mul(x_2, x_3);
+    let t = c.sqr(x_5, 1);
+    let x_13 = c.mul(t, x_3);
+    let t = c.sqr(x_13, 1);
+    let x_39 = c.mul(t, x_13);
+    let x_41 = c.mul(x_2, x_39);
+    let t = c.mul(x_3, x_13);
+    let x_57 = c.mul(t, x_41);
+    let x_59 = c.mul(x_2, x_57);
+    let t = c.sqr(x_41, 1);
+    let x_95 = c.mul(t, x_13);
+    let t = c.sqr(x_57, 1);
+    let x_127 = c.mul(t, x_13);
+    let t = c.sqr(x_127, 1);
+    let x_255 = c.mul(t, X);
+    let table = [
+        (1, X), (3, x_3), (5, x_5), (13, x_13), (39, x_39), (41, x_41), (57, x_57), (59, x_59),
+        (95, x_95), (127, x_127), (255, x_255),
+    ];
+    let steps = [
+        (1, 1), (6, 59), (3, 3), (7, 39), (4, 5), (4, 3), (8, 41), (6, 39), (8, 95), (7, 41),
+        (7, 3), (3, 1), (6, 39), (8, 59), (8, 1), (8, 1), (6, 13), (10, 59), (11, 5), (4, 5),
+        (8, 59), (4, 13), (6, 41), (15, 95), (7, 127), (6, 57), (5, 13), (8, 255), (8, 127),
+        (8, 255), (8, 255), (8, 255), (9, 255), (8, 255), (8, 255), (8, 255),
+    ];
+    append_windows(&mut c, &table, 57, &steps);
+    c
+}
+
+// Derives an addition chain for the exponent e > 0 by the sliding window
+// method: going from the top bit, each window is the longest run of at most
+// w bits starting and ending with 1. All the window sizes up to 8 are tried,
+// and the shortest chain is returned
+fn generate_chain(e: &U384) -> Chain {
+    (1..=8).map(|w| {
+        // The windows as (value, position of the lowest bit)
+        let (mut windows, mut i) = (vec![], bits(e) as i32 - 1);
+        while i >= 0 {
+            if !bit(e, i as usize) { i -= 1; continue; }
+            let mut j = (i - w + 1).max(0);
+            while !bit(e, j as usize) { j += 1; }
+            let value = (j..=i).rev().fold(0, |v, k| 2 * v + bit(e, k as usize) as u32);
+            windows.push((value, j as u32));
+            i = j - 1;
+        }
+        let (first, mut low) = windows[0];
+        let mut steps: Vec<(u32, u32)> = windows[1..].iter().map(|&(v, j)| {
+            let s = low - j;
+            low = j;
+            (s, v)
+        }).collect();
+        if low > 0 { steps.push((low, 0)); }
+        window_chain(first, &steps)
+    })
+    .min_by_key(|c| { let (m, s) = c.cost(); m + s })
+    .unwrap()
+}
+
+// Computes the multiplicative inverse of x modulo a prime p as x^(p - 2) by
+// the given chain for p - 2; for x = 0 the result is 0. The chain must
+// compute p - 2, e.g. one of the written-out chains or the output of
+// generate_chain; the primality of p is not checked
+fn fermat_inv(x: &U384, chain: &Chain, p: &U384) -> U384 {
+    chain.pow(x, p)
+}
+
+// Returns a if mask is all ones, and b if mask is 0, limb by limb
+fn select(mask: u64, a: &U384, b: &U384) -> U384 {
+    let mut r = ZERO;
+    for i in 0..6 { r[i] = b[i] ^ (mask & (a[i] ^ b[i])); }
+    r
+}
+
+// Computes a + b mod n for a, b < n without branches: the difference s - n
+// is taken if the sum overflowed or if there was no borrow
+fn add_mod(a: &U384, b: &U384, n: &U384) -> U384 {
+    let (s, carry) = add(a, b);
+    let (d, borrow) = sub(&s, n);
+    select(((carry | !borrow) as u64).wrapping_neg(), &d, &s)
+}
+
+// Computes a * b mod n for a, b < n by the double-and-add method without
+// branches on the bits of a and b; the number of iterations depends only on n
+fn mul_mod(a: &U384, b: &U384, n: &U384) -> U384 {
+    let mut r = ZERO;
+    for i in (0..bits(n)).rev() {
+        r = add_mod(&r, &r, n);
+        r = select((bit(b, i) as u64).wrapping_neg(), &add_mod(&r, a, n), &r);
+    }
+    r
+}
+
+fn main() {
+    let mut state = 2023;
+    let fields = [
+        ("BN254 q", BN254_Q, bn254_q_chain()),
+        ("BN254 r", BN254_R, bn254_r_chain()),
+        ("BLS12-381 p", BLS12_381_P, bls12_381_p_chain()),
+        ("BLS12-381 r", BLS12_381_R, bls12_381_r_chain()),
+        ("secp256k1 p", SECP256K1_P, secp256k1_chain()),
+    ];
+    for (name, p, fixed) in fields {
+        let p_minus_2 = sub(&p, &small(2)).0;
+        let generated = generate_chain(&p_minus_2);
+        assert!(generated.exponent() == p_minus_2, "The generated chain for {} does not compute p - 2!", name);
+        // The written-out chain computes p - 2 with fewer operations than the
+        // generated one
+        let (fc, gc) = (fixed.cost(), generated.cost());
+        assert!(fixed.exponent() == p_minus_2, "The written-out chain for {} does not compute p - 2!", name);
+        assert!(fc.0 + fc.1 < gc.0 + gc.1, "The written-out chain for {} is not shorter than the generated one!", name);
+        println!("{}: (mul, sqr) = {:?} written out, {:?} generated", name, fc, gc);
+        for chain in [&fixed, &generated] {
+            for x in [ONE, sub(&p, &ONE).0] {
+                assert!(fermat_inv(&x, chain, &p) == x, "Incorrect inverse of +-1 modulo {}!", name);
+            }
+            for _ in 0..10 {
+                let x = random_below(&p, &mut state);
+                assert!(fermat_inv(&x, chain, &p) == mod_inv(&x, &p).unwrap(), "Mismatch with mod_inv modulo {}!", name);
+            }
+        }
+    }
+    // The generator for other primes: the Mersenne primes 2^61 - 1 and
+    // 2^127 - 1 and the Goldilocks prime 2^64 - 2^32 + 1
+    for p in [[(1 << 61) - 1, 0, 0, 0, 0, 0], [u64::MAX, u64::MAX >> 1, 0, 0, 0, 0], [0xffffffff00000001, 0, 0, 0, 0, 0]] {
+        let chain = generate_chain(&sub(&p, &small(2)).0);
+        for _ in 0..10 {
+            let x = random_below(&p, &mut state);
+            assert!(fermat_inv(&x, &chain, &p) == mod_inv(&x, &p).unwrap(), "Mismatch with mod_inv!");
+        }
+    }
+}
@@ -0,0 +1,208 @@
+/*
+    The GLV Scalar Decomposition by Means of the Extended Euclidean Algorithm:
+                       Implementation in Rust and Proof
+
+                               October 2026
+*/
+// On BN254 G1 and secp256k1 there is an endomorphism phi, which is cheap to
+// compute and acts as phi(P) = lambda * P for a cube root of unity lambda
+// modulo the group order n. Gallant, Lambert and Vanstone split a scalar k
+// into k = k_1 + k_2 * lambda (mod n) with k_1 and k_2 of about half the bits
+// of n, so k * P = k_1 * P + k_2 * phi(P) needs half as many doublings. The
+// pairs (a, b) with a + b * lambda = 0 (mod n) form a lattice, and the
+// decomposition subtracts from (k, 0) a close lattice vector, so it needs a
+// basis of short vectors. Such a basis is given by the classic mod_inv loop
+// on (lambda, n) stopped at sqrt(n) (Guide to Elliptic Curve Cryptography,
+// Algorithm 3.74); the values are 256-bit numbers, so they are the
+// arbitrary-size integers of Big.rs, as in "Proof and Implementation of
+// Continued Fraction Expansion.rs"
+use std::cmp::Ordering;
+
+// The arbitrary-size integers of Big.rs
+#[allow(dead_code)]
+mod big {
+    include!("Big.rs");
+}
+
+use big::*;
+
+// A signed integer stored as its sign and its magnitude; 0 is not negative
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Int {
+    neg: bool,
+    mag: Big,
+}
+
+fn int(mag: Big) -> Int {
+    Int { neg: false, mag }
+}
+
+fn neg(a: &Int) -> Int {
+    Int { neg: !a.neg && !a.mag.is_empty(), mag: a.mag.clone() }
+}
+
+fn int_add(a: &Int, b: &Int) -> Int {
+    if a.neg == b.neg { return Int { neg: a.neg, mag: add(&a.mag, &b.mag) }; }
+    // The signs differ, so the magnitude of the sum is the difference of the
+    // magnitudes, and the sign is that of the larger one
+    if cmp(&a.mag, &b.mag) == Ordering::Less { return Int { neg: b.neg, mag: sub(&b.mag, &a.mag) }; }
+    let mag = sub(&a.mag, &b.mag);
+    Int { neg: a.neg && !mag.is_empty(), mag }
+}
+
+fn int_sub(a: &Int, b: &Int) -> Int {
+    int_add(a, &neg(b))
+}
+
+fn int_mul(a: &Int, b: &Int) -> Int {
+    let mag = mul(&a.mag, &b.mag);
+    Int { neg: a.neg != b.neg && !mag.is_empty(), mag }
+}
+
+// Returns a / b rounded to the nearest integer, the halves away from 0, for
+// b != 0: |a / b| + 1/2 = (2 * |a| + |b|) / (2 * |b|)
+fn round_div(a: &Int, b: &Int) -> Int {
+    let mag = divrem(&add(&add(&a.mag, &a.mag), &b.mag), &add(&b.mag, &b.mag)).0;
+    Int { neg: a.neg != b.neg && !mag.is_empty(), mag }
+}
+
+// Returns the remainder of a modulo n, which is in [0, n) for negative a too
+fn reduce(a: &Int, n: &Big) -> Big {
+    let r = divrem(&a.mag, n).1;
+    if a.neg && !r.is_empty() { sub(n, &r) } else { r }
+}
+
+// Returns the squared Euclidean norm of v
+fn norm2(v: &(Int, Int)) -> Big {
+    add(&mul(&v.0.mag, &v.0.mag), &mul(&v.1.mag, &v.1.mag))
+}
+
+// The basis v_1 = (a_1, b_1), v_2 = (a_2, b_2) of the lattice of the pairs
+// (a, b) with a + b * lambda = 0 (mod n), and its determinant
+// a_1 * b_2 - a_2 * b_1, which is n or -n
+struct Glv {
+    n: Big,
+    lambda: Big,
+    v1: (Int, Int),
+    v2: (Int, Int),
+    det: Int,
+}
+
+impl Glv {
+    // Derives the basis for a prime n and 1 < lambda < n; the primality is
+    // not checked, but a lambda that is not coprime with n is detected
+    fn new(n: &Big, lambda: &Big) -> Glv {
+        let (mut s, mut x_s, mut b, mut x_b) = (divrem(lambda, n).1, int(vec![1]), n.clone(), int(vec![]));
+        let step = |s: &Big, x_s: &Int, b: &Big, x_b: &Int| {
+            let (q, r) = divrem(b, s);
+            (r, int_sub(x_b, &int_mul(&int(q), x_s)))
+        };
+        // This is the loop of the classic mod_inv on lambda and n with the
+        // signed coefficients x_s and x_b, so we have
+        // (1) s = x_s * lambda + n_s * n; (2) b = x_b * lambda + n_b * n;
+        // that is, (s, -x_s) and (b, -x_b) are in the lattice. Also
+        // (3) b * |x_s| + s * |x_b| = n: it holds now, since x_b = 0 and
+        // x_s = 1, and each step gives |x_next| = |x_b| + q * |x_s| (the signs
+        // of x_s and x_b alternate), so b * |x_s| + s * |x_b| turns into
+        // s * (|x_b| + q * |x_s|) + (b - q * s) * |x_s| = s * |x_b| + b * |x_s|.
+        // The loop stops at the first remainder s below sqrt(n), so b = r_m is
+        // the last remainder that is at least sqrt(n) and s = r_(m + 1)
+        while cmp(&mul(&s, &s), n) != Ordering::Less {
+            let (r, x_r) = step(&s, &x_s, &b, &x_b);
+            (s, x_s, b, x_b) = (r, x_r, s, x_s);
+        }
+        // If s = 0, then GCD(lambda, n) = b >= sqrt(n) > 1. Otherwise one more
+        // step gives r_(m + 2). Due to (3), |x_s| <= n / b <= sqrt(n), so
+        // v_1 = (r_(m + 1), -x_s) has both coordinates below sqrt(n) (up to
+        // 1). The shorter of (r_m, -x_b) and (r_(m + 2), -x_r) is v_2; it is
+        // short for the GLV curves, but this is not guaranteed in general, so
+        // the bounds of decompose are in terms of both v_1 and v_2
+        assert!(!s.is_empty(), "Lambda and n are not coprime!");
+        let (r, x_r) = step(&s, &x_s, &b, &x_b);
+        let v1 = (int(s), neg(&x_s));
+        let (c1, c2) = ((int(b), neg(&x_b)), (int(r), neg(&x_r)));
+        let v2 = if cmp(&norm2(&c2), &norm2(&c1)) == Ordering::Less { c2 } else { c1 };
+        // For consecutive remainders, a_1 * b_2 - a_2 * b_1 = -+(b * x_s -
+        // s * x_b) = -+n by (3), and (r_(m + 2), x_r) = (r_m, x_b) - q *
+        // (r_(m + 1), x_s) does not change the determinant. Thus, v_1 and v_2
+        // span a sublattice of index 1, which is the whole lattice
+        let det = int_sub(&int_mul(&v1.0, &v2.1), &int_mul(&v2.0, &v1.1));
+        assert!(det.mag == *n, "The vectors do not form a basis of the lattice!");
+        Glv { n: n.clone(), lambda: lambda.clone(), v1, v2, det }
+    }
+
+    // Returns (k_1, k_2) with k_1 + k_2 * lambda = k (mod n) for 0 <= k < n,
+    // where 2 * |k_1| <= |a_1| + |a_2| and 2 * |k_2| <= |b_1| + |b_2|
+    fn decompose(&self, k: &Big) -> (Int, Int) {
+        assert!(cmp(k, &self.n) == Ordering::Less, "The scalar is not reduced!");
+        let ((a1, b1), (a2, b2)) = (&self.v1, &self.v2);
+        let k = int(k.clone());
+        // By Cramer's rule, (k, 0) = beta_1 * v_1 + beta_2 * v_2 for the
+        // rationals beta_1 = k * b_2 / det and beta_2 = -k * b_1 / det. With
+        // c_i the nearest integer to beta_i, the lattice vector
+        // c_1 * v_1 + c_2 * v_2 is close to (k, 0), and the difference
+        // (k_1, k_2) = (beta_1 - c_1) * v_1 + (beta_2 - c_2) * v_2
+        // satisfies k_1 + k_2 * lambda = k (mod n), since the lattice vectors
+        // add 0. As |beta_i - c_i| <= 1/2, |k_1| <= (|a_1| + |a_2|) / 2 and
+        // |k_2| <= (|b_1| + |b_2|) / 2
+        let c1 = round_div(&int_mul(&k, b2), &self.det);
+        let c2 = round_div(&neg(&int_mul(&k, b1)), &self.det);
+        let k1 = int_sub(&int_sub(&k, &int_mul(&c1, a1)), &int_mul(&c2, a2));
+        let k2 = neg(&int_add(&int_mul(&c1, b1), &int_mul(&c2, b2)));
+        assert!(cmp(&add(&k1.mag, &k1.mag), &add(&a1.mag, &a2.mag)) != Ordering::Greater, "k_1 is out of bounds!");
+        assert!(cmp(&add(&k2.mag, &k2.mag), &add(&b1.mag, &b2.mag)) != Ordering::Greater, "k_2 is out of bounds!");
+        (k1, k2)
+    }
+
+    // Checks that k_1 + k_2 * lambda = k (mod n)
+    fn recombines(&self, k: &Big, (k1, k2): &(Int, Int)) -> bool {
+        reduce(&int_add(k1, &int_mul(k2, &int(self.lambda.clone()))), &self.n) == *k
+    }
+}
+
+include!("SplitMix64.rs");
+
+fn main() {
+    // Exhaustively for the primes n = 1 (mod 3) below 1000, which have the
+    // cube roots of unity, and all scalars k
+    for n in (7..1000u64).filter(|&n| n % 3 == 1 && (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0)) {
+        let lambda = (2..n).find(|&l| (l * l + l + 1) % n == 0).unwrap();
+        let glv = Glv::new(&vec![n], &vec![lambda]);
+        for k in 0..n {
+            let k = norm(vec![k]);
+            assert!(glv.recombines(&k, &glv.decompose(&k)), "Incorrect decomposition!");
+        }
+    }
+    // The secp256k1 basis is the one hardcoded in libsecp256k1
+    let glv = Glv::new(&from_hex(SECP256K1_N), &from_hex(SECP256K1_LAMBDA));
+    let a1 = int(from_hex("3086d221a7d46bcde86c90e49284eb15"));
+    let b1 = neg(&int(from_hex("e4437ed6010e88286f547fa90abfe4c3")));
+    let a2 = int(from_hex("114ca50f7a8e2f3f657c1108d9d44cfd8"));
+    assert!(glv.v1 == (a1.clone(), b1) && glv.v2 == (a2, a1), "Incorrect secp256k1 basis!");
+    // Random scalars on both curves, with both non-trivial cube roots of
+    // unity on BN254; the halves fit in 128 bits (with the sign)
+    let mut state = 2023;
+    let bn254 = (from_hex(BN254_R), from_hex(BN254_LAMBDA));
+    let curves = [
+        ("BN254", bn254.0.clone(), bn254.1.clone()),
+        ("BN254 (lambda^2)", bn254.0.clone(), divrem(&mul(&bn254.1, &bn254.1), &bn254.0).1),
+        ("secp256k1", from_hex(SECP256K1_N), from_hex(SECP256K1_LAMBDA)),
+    ];
+    for (name, n, lambda) in curves {
+        let glv = Glv::new(&n, &lambda);
+        let mut max_bits = 0;
+        let mut scalars = vec![vec![], vec![1], sub(&n, &vec![1]), lambda.clone()];
+        for _ in 0..1000 {
+            let k: Big = norm((0..4).map(|_| next_u64(&mut state)).collect());
+            scalars.push(divrem(&k, &n).1);
+        }
+        for k in scalars {
+            let (k1, k2) = glv.decompose(&k);
+            assert!(glv.recombines(&k, &(k1.clone(), k2.clone())), "Incorrect decomposition!");
+            max_bits = max_bits.max(bits(&k1.mag)).max(bits(&k2.mag));
+        }
+        assert!(max_bits <= 128, "The halves are too long!");
+        println!("{}: basis of {}, {}, {}, {} bits; the halves have at most {} bits",
+            name, bits(&glv.v1.0.mag), bits(&glv.v1.1.mag), bits(&glv.v2.0.mag), bits(&glv.v2.1.mag), max_bits);
+    }
+}
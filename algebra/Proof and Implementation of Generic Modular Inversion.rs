@@ -0,0 +1,158 @@
+/*
+   The Modular Inversion for All Primitive Integer Types by Means of the
+     Classic and Binary Extended Euclidean Algorithms: Implementation in
+                               Rust and Proof
+
+                               October 2026
+*/
+// The Word trait of the unsigned primitive types and the loop of the binary
+// algorithm with its proof
+include!("Word.rs");
+
+// The algorithm used by ModInverse: the classic Extended Euclidean Algorithm
+// (any modulus n >= 2) or the binary one (odd moduli only)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Backend {
+    Euclidean,
+    Binary,
+}
+
+// Computes the multiplicative inverse of self modulo n with the given backend.
+// The result lies in [0, n); if self and n are not coprime, the inverse does
+// not exist, so None is returned. Panics in the case of n < 2, and in the case
+// of even n for the binary backend
+trait ModInverse: Sized {
+    fn mod_inverse(self, n: Self, backend: Backend) -> Option<Self>;
+}
+
+// The classic mod_inv for 0 <= x < n working with unsigned values only. The
+// signed version stores x_s and x_b, which can be negative. For any two
+// consecutive coefficients the signs alternate: initially x_b = 0 and
+// x_s = 1, and if x_b and x_s have opposite signs (0 counting as either),
+// then x_b - q * x_s has the sign of x_b and |x_b - q * x_s| =
+// |x_b| + q * |x_s|. Thus, we store the absolute values and the sign of x_s,
+// which flips in each iteration. The absolute values of all the coefficients
+// do not exceed n (see the proof for the classic mod_inv), so the sum
+// |x_b| + q * |x_s| does not overflow
+fn euclidean_inv<T: Word>(x: T, n: T) -> Option<T> {
+    let (mut s, mut x_s, mut b, mut x_b, mut s_neg) = (x, T::ONE, n, T::ZERO, false);
+    while s > T::ZERO {
+        let q = b / s;
+        (s, x_s, b, x_b, s_neg) = (b - q * s, x_b + q * x_s, s, x_s, !s_neg);
+    }
+    // Now b = GCD(x, n), and x_b is negative if and only if x_s is positive
+    // and x_b is not 0; in this case its value modulo n is n - |x_b|
+    if b != T::ONE { return None; }
+    Some(if !s_neg && x_b > T::ZERO { n - x_b } else { x_b })
+}
+
+// The binary mod_inv for 0 <= x < n and odd n working with unsigned values
+// only, as in "Proof and Implementation of Unsigned Binary Euclidean
+// Inversion.rs", whose loop binary_loop of Word.rs it runs. Unlike the
+// unchecked u64 version, it checks that x and n are coprime: b = GCD(x, n)
+// when the loop ends
+fn binary_inv<T: Word>(x: T, n: T) -> Option<T> {
+    let (b, v) = binary_loop(x, n);
+    if b == T::ONE { Some(v) } else { None }
+}
+
+impl<T: Word> ModInverse for T {
+    fn mod_inverse(self, n: Self, backend: Backend) -> Option<Self> {
+        if n <= T::ONE { panic!("The modulus must be greater than 1!"); }
+        let x = self % n;
+        match backend {
+            Backend::Euclidean => euclidean_inv(x, n),
+            Backend::Binary => {
+                if !n.is_odd() { panic!("The modulus must be odd for the binary backend!"); }
+                binary_inv(x, n)
+            }
+        }
+    }
+}
+
+// A signed x is reduced to its residue in [0, n) and inverted as an unsigned
+// value of the same width; since the result is less than n, it fits back
+macro_rules! impl_signed {
+    ($($i:ty => $u:ty),*) => {$(
+        impl ModInverse for $i {
+            fn mod_inverse(self, n: Self, backend: Backend) -> Option<Self> {
+                if n < 2 { panic!("The modulus must be greater than 1!"); }
+                let r = self.unsigned_abs() % n as $u;
+                let x = if self < 0 && r > 0 { n as $u - r } else { r };
+                x.mod_inverse(n as $u, backend).map(|v| v as $i)
+            }
+        }
+    )*};
+}
+
+impl_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+// Computes a * b mod n for a, b < n in u128 by the double-and-add method,
+// since the product itself may not fit
+fn mul_mod_u128(a: u128, b: u128, n: u128) -> u128 {
+    let add_mod = |p: u128, q: u128| if p >= n - q { p - (n - q) } else { p + q };
+    let mut r = 0;
+    for i in (0..128).rev() {
+        r = add_mod(r, r);
+        if (b >> i) & 1 == 1 { r = add_mod(r, a); }
+    }
+    r
+}
+
+include!("SplitMix64.rs");
+
+fn main() {
+    // All pairs of u8 and i8 values with n >= 2 against the definition, and
+    // both backends against each other for odd n
+    for n in 2..=u8::MAX {
+        for x in 0..=u8::MAX {
+            let expected = (0..n).find(|&i| (i as u32 * x as u32) % n as u32 == 1);
+            assert!(x.mod_inverse(n, Backend::Euclidean) == expected, "Incorrect inverse!");
+            if n & 1 == 1 {
+                assert!(x.mod_inverse(n, Backend::Binary) == expected, "Incorrect inverse!");
+            }
+            if n <= i8::MAX as u8 {
+                let (x, n) = (x as i8, n as i8);
+                let residue = (x as i32).rem_euclid(n as i32) as u8;
+                let expected = residue.mod_inverse(n as u8, Backend::Euclidean).map(|v| v as i8);
+                assert!(x.mod_inverse(n, Backend::Euclidean) == expected, "Incorrect inverse!");
+            }
+        }
+    }
+    // Random values for the wider types; the largest primes below 2^16, 2^32,
+    // 2^63 and 2^64, as well as 2^64 - 1 = 3 * 5 * 17 * 257 * 641 * 65537 *
+    // 6700417, which has non-invertible residues
+    let mut state = 2023;
+    for _ in 0..10_000 {
+        let r = next_u64(&mut state);
+        let (x, n) = (r as u16, u16::MAX - 14);
+        if let Some(i) = x.mod_inverse(n, Backend::Binary) {
+            assert!((i as u32 * x as u32) % n as u32 == 1, "Incorrect inverse!");
+        }
+        let (x, n) = (r as u32, u32::MAX - 4);
+        assert!(x.mod_inverse(n, Backend::Binary) == x.mod_inverse(n, Backend::Euclidean), "Mismatch!");
+        let (x, n) = (r as i64, i64::MAX - 24);
+        let i = x.mod_inverse(n, Backend::Euclidean).unwrap();
+        assert!((i as i128 * x as i128).rem_euclid(n as i128) == 1, "Incorrect inverse!");
+        assert!(Some(i) == x.mod_inverse(n, Backend::Binary), "Mismatch!");
+        for n in [u64::MAX - 58, u64::MAX] {
+            let (e, b) = (r.mod_inverse(n, Backend::Euclidean), r.mod_inverse(n, Backend::Binary));
+            assert!(e == b, "Mismatch!");
+            if let Some(i) = e {
+                assert!((i as u128 * r as u128) % n as u128 == 1, "Incorrect inverse!");
+            }
+        }
+        // The Mersenne prime 2^127 - 1 and the largest prime below 2^128
+        let x = ((r as u128) << 64) | next_u64(&mut state) as u128;
+        for n in [(1 << 127) - 1, u128::MAX - 158] {
+            let i = x.mod_inverse(n, Backend::Euclidean).unwrap();
+            assert!(mul_mod_u128(i, x % n, n) == 1, "Incorrect inverse!");
+            assert!(Some(i) == x.mod_inverse(n, Backend::Binary), "Mismatch!");
+        }
+    }
+    let (x, n) = (-3i64, 10);
+    match x.mod_inverse(n, Backend::Euclidean) {
+        Some(r) => println!("{}", r),
+        None => println!("{} and {} are not coprime!", x, n),
+    }
+}
@@ -0,0 +1,250 @@
+/*
+  The Modular Inversion for Large Integers by Means of the Half-GCD Algorithm:
+                       Implementation in Rust and Proof
+
+                               October 2026
+*/
+// The classic mod_inv performs O(n) iterations on n-bit numbers, each costing
+// O(n), so it is quadratic. The half-GCD algorithm (Schonhage) finds the
+// quotients that reduce a and b to half of their size recursively from the
+// leading halves of a and b, so most of the work is done on small numbers,
+// and the full numbers are only touched by a few 2x2 matrix products. Big.rs
+// multiplies by the Karatsuba method and, from NTT_THRESHOLD limbs on
+// (2^18 bits, where the NTT becomes faster), by the NTT over the Goldilocks
+// field in O(n log n), so the cost of the inversion is quasi-linear,
+// O(n log^2 n). Compile with -O: main runs a benchmark against the classic
+// mod_inv and prints the crossover point
+use std::cmp::Ordering;
+use std::time::Instant;
+
+// The arbitrary-size integers of Big.rs
+#[allow(dead_code)]
+mod big {
+    include!("Big.rs");
+}
+
+use big::*;
+
+// Numbers shorter than this (in bits) are reduced by the classic steps only
+const HGCD_THRESHOLD: usize = 2048;
+
+// A reduction of (a, b), a > b, by the first j quotients q_1, ..., q_j of the
+// Euclidean algorithm: (a, b) = M * (alpha, beta), where M = E(q_1) * ... *
+// E(q_j) with E(q) = ((q, 1), (1, 0)), and alpha > beta are the j-th and
+// (j + 1)-th remainders. M is stored as (m_11, m_12, m_21, m_22); its entries
+// are non-negative and its determinant is (-1)^j
+struct Reduction {
+    m: [Big; 4],
+    qs: Vec<Big>,
+    alpha: Big,
+    beta: Big,
+}
+
+fn identity(a: &Big, b: &Big) -> Reduction {
+    Reduction { m: [vec![1], vec![], vec![], vec![1]], qs: vec![], alpha: a.clone(), beta: b.clone() }
+}
+
+fn mat_mul(x: &[Big; 4], y: &[Big; 4]) -> [Big; 4] {
+    [
+        add(&mul(&x[0], &y[0]), &mul(&x[1], &y[2])),
+        add(&mul(&x[0], &y[1]), &mul(&x[1], &y[3])),
+        add(&mul(&x[2], &y[0]), &mul(&x[3], &y[2])),
+        add(&mul(&x[2], &y[1]), &mul(&x[3], &y[3])),
+    ]
+}
+
+// Performs one step of the classic Euclidean algorithm for beta > 0: alpha =
+// q * beta + r, so (alpha, beta) = E(q) * (beta, r) and M becomes M * E(q)
+fn step(red: &mut Reduction) {
+    let (q, r) = divrem(&red.alpha, &red.beta);
+    let [m_11, m_12, m_21, m_22] = &red.m;
+    red.m = [add(&mul(m_11, &q), m_12), m_11.clone(), add(&mul(m_21, &q), m_22), m_21.clone()];
+    red.alpha = std::mem::replace(&mut red.beta, r);
+    red.qs.push(q);
+}
+
+// Returns x - y if positive is true and y - x otherwise, if it is non-negative
+fn signed_diff(x: Big, y: Big, positive: bool) -> Option<Big> {
+    let (x, y) = if positive { (x, y) } else { (y, x) };
+    if cmp(&x, &y) == Ordering::Less { None } else { Some(sub(&x, &y)) }
+}
+
+// Returns (alpha', beta') = M^(-1) * (alpha, beta) for M with determinant
+// (-1)^j, i.e. alpha' = (-1)^j * (m_22 * alpha - m_12 * beta) and
+// beta' = (-1)^j * (m_11 * beta - m_21 * alpha), or None if one of them is
+// negative
+fn apply_inverse(m: &[Big; 4], j: usize, alpha: &Big, beta: &Big) -> Option<(Big, Big)> {
+    let alpha_new = signed_diff(mul(&m[3], alpha), mul(&m[1], beta), j.is_multiple_of(2))?;
+    let beta_new = signed_diff(mul(&m[0], beta), mul(&m[2], alpha), j.is_multiple_of(2))?;
+    Some((alpha_new, beta_new))
+}
+
+// Continues red by the quotients qs with the product m, which were computed
+// from the leading bits of red.alpha and red.beta and are therefore only
+// approximately the next quotients, keeping their longest prefix that is
+// correct. The correctness criterion: let (alpha', beta') = M^(-1) *
+// (alpha, beta) for M = E(q_1) * ... * E(q_j) with q_i >= 1. If
+// alpha' > beta' > 0 (or beta' = 0 and q_j >= 2), then q_1, ..., q_j are
+// the first j quotients of (alpha, beta). Indeed, denote r_j = alpha' and
+// r_(j+1) = beta', and going up r_(i-1) = q_i * r_i + r_(i+1). We have
+// 0 <= r_(j+1) < r_j and, by induction, if 0 <= r_(i+1) < r_i, then
+// r_(i-1) > r_i, since either q_i >= 2 or r_(i+1) > 0 (for i < j it is
+// positive, since the remainders grow going up). Thus, each r_(i+1) is the
+// remainder and q_i the quotient of r_(i-1) / r_i, and r_(-1), r_0 are
+// alpha and beta. Conversely, the true quotients satisfy the criterion.
+// A prefix of a correct sequence is correct as well, so we drop the last
+// quotient until the criterion holds; this needs the matrix
+// M * E(q_j)^(-1) = ((m_12, m_11 - q_j * m_12), (m_22, m_21 - q_j * m_22))
+fn extend(red: &mut Reduction, mut m: [Big; 4], mut qs: Vec<Big>) {
+    loop {
+        if let Some((alpha, beta)) = apply_inverse(&m, qs.len(), &red.alpha, &red.beta) {
+            let last_ok = !beta.is_empty() || qs.last().is_none_or(|q| bits(q) >= 2);
+            if cmp(&alpha, &beta) == Ordering::Greater && last_ok {
+                red.m = mat_mul(&red.m, &m);
+                red.qs.extend(qs);
+                (red.alpha, red.beta) = (alpha, beta);
+                return;
+            }
+        }
+        // The empty sequence always satisfies the criterion, since
+        // red.alpha > red.beta, so there is a quotient to drop here
+        let q = qs.pop().unwrap();
+        let [m_11, m_12, m_21, m_22] = m;
+        m = [m_12.clone(), sub(&m_11, &mul(&q, &m_12)), m_22.clone(), sub(&m_21, &mul(&q, &m_22))];
+    }
+}
+
+// Reduces (a, b), a > b, by the first quotients of the Euclidean algorithm
+// until beta < 2^s <= alpha for s = ceil(bits(a) / 2), i.e. halves the size.
+// The leading bits of a and b determine the first quotients: the quotients
+// of (a / 2^s, b / 2^s), reduced to half of their size, are mostly the
+// quotients of (a, b), which reduces (a, b) to about 3/4 of their size. The
+// same is then repeated for the leading bits of the result. Since extend keeps
+// only the correct quotients, and the classic steps finish the reduction,
+// the result is always correct, and the approximation only affects speed
+fn hgcd(a: &Big, b: &Big) -> Reduction {
+    let mut red = identity(a, b);
+    let s = bits(a).div_ceil(2);
+    if cmp(a, b) != Ordering::Greater || bits(b) <= s { return red; }
+    if bits(a) > HGCD_THRESHOLD {
+        let top = hgcd(&shr(a, s), &shr(b, s));
+        extend(&mut red, top.m, top.qs);
+        if bits(&red.beta) > s {
+            step(&mut red);
+            // The top parts of alpha and beta above 2^k have
+            // 2 * (bits(alpha) - s) bits, and halving them leaves about s bits
+            // in the full numbers
+            if bits(&red.beta) > s {
+                let k = 2 * s - bits(&red.alpha);
+                let top = hgcd(&shr(&red.alpha, k), &shr(&red.beta, k));
+                extend(&mut red, top.m, top.qs);
+            }
+        }
+    }
+    while bits(&red.beta) > s { step(&mut red); }
+    red
+}
+
+// Computes the multiplicative inverse of x modulo n >= 2 by applying the
+// half-GCD algorithm; the result is the same as for the classic mod_inv. If
+// x and n are not coprime, the inverse does not exist, so None is returned
+fn mod_inv(x: &Big, n: &Big) -> Option<Big> {
+    let mut red = identity(n, &divrem(x, n).1);
+    while !red.beta.is_empty() {
+        if bits(&red.alpha) > HGCD_THRESHOLD {
+            let half = hgcd(&red.alpha, &red.beta);
+            red.m = mat_mul(&red.m, &half.m);
+            red.qs.extend(half.qs);
+            (red.alpha, red.beta) = (half.alpha, half.beta);
+        }
+        if !red.beta.is_empty() { step(&mut red); }
+    }
+    // Now beta = 0 and alpha = GCD(x, n). Since alpha = (-1)^j *
+    // (m_22 * n - m_12 * x), for alpha = 1 we have m_12 * x = (-1)^(j + 1)
+    // (mod n), so the inverse is m_12 for odd j and -m_12 for even j. Like
+    // the coefficients of the classic mod_inv, m_12 does not exceed n
+    if red.alpha != [1] { return None; }
+    let m_12 = &red.m[1];
+    Some(if red.qs.len() % 2 == 1 || m_12.is_empty() { m_12.clone() } else { sub(n, m_12) })
+}
+
+// The classic mod_inv for Big, working with the absolute values of the
+// coefficients as in "Proof and Implementation of Generic Modular
+// Inversion.rs"
+fn classic_mod_inv(x: &Big, n: &Big) -> Option<Big> {
+    let (mut s, mut x_s, mut b, mut x_b, mut s_neg) = (divrem(x, n).1, vec![1], n.clone(), vec![], false);
+    while !s.is_empty() {
+        let (q, r) = divrem(&b, &s);
+        let x_next = add(&x_b, &mul(&q, &x_s));
+        (s, x_s, b, x_b, s_neg) = (r, x_next, s, x_s, !s_neg);
+    }
+    if b != [1] { return None; }
+    Some(if !s_neg && !x_b.is_empty() { sub(n, &x_b) } else { x_b })
+}
+
+include!("SplitMix64.rs");
+
+// Returns a random number of exactly the given number of bits
+fn random(bits: usize, state: &mut u64) -> Big {
+    let mut r: Big = (0..bits.div_ceil(64)).map(|_| next_u64(state)).collect();
+    let top = r.last_mut().unwrap();
+    *top >>= (64 - bits % 64) % 64;
+    *top |= 1 << ((bits - 1) % 64);
+    r
+}
+
+fn main() {
+    let mut state = 2023;
+    // Correctness: random pairs of all sizes, coprime or not
+    for i in 0..300 {
+        let n = random(64 + 61 * i, &mut state);
+        let x = random(1 + (next_u64(&mut state) % bits(&n) as u64) as usize, &mut state);
+        let x = if i % 3 == 0 { mul(&x, &vec![6]) } else { x };
+        let n = if i % 3 == 0 { mul(&n, &vec![10]) } else { n };
+        let inv = mod_inv(&x, &n);
+        assert!(inv == classic_mod_inv(&x, &n), "Mismatch with the classic mod_inv!");
+        if let Some(inv) = inv {
+            assert!(divrem(&mul(&x, &inv), &n).1 == [1], "Incorrect inverse!");
+        }
+    }
+    assert!(mod_inv(&vec![], &vec![7]).is_none(), "Zero is not invertible!");
+    // The NTT multiplication against the Karatsuba one, also for the numbers
+    // of all ones, which maximize the coefficients of the convolution
+    for limbs in [1, 2, 5, 33, 100, 777] {
+        let a = random(64 * limbs, &mut state);
+        let b = random(64 * limbs - 13, &mut state);
+        assert!(mul_ntt(&a, &b) == mul(&a, &b), "Incorrect NTT multiplication!");
+        let ones = vec![u64::MAX; limbs];
+        assert!(mul_ntt(&ones, &ones) == mul(&ones, &ones), "Incorrect NTT multiplication!");
+    }
+    // A size where the matrix products of the top level use the NTT;
+    // n = x * k + 1 is coprime with x
+    let x = random(64 * 4 * NTT_THRESHOLD - 100, &mut state);
+    let n = add(&mul(&x, &random(200, &mut state)), &vec![1]);
+    let inv = mod_inv(&x, &n).expect("x and n are coprime!");
+    assert!(divrem(&mul(&x, &inv), &n).1 == [1], "Incorrect inverse!");
+    // Benchmark
+    println!("{:>8} {:>14} {:>14}", "bits", "classic (us)", "half-gcd (us)");
+    let mut crossover = None;
+    for log in 8..=16 {
+        let bits = 1 << log;
+        let (n, x) = (random(bits, &mut state), random(bits - 1, &mut state));
+        let runs = (1 << 19) / bits;
+        let start = Instant::now();
+        for _ in 0..runs { classic_mod_inv(&x, &n); }
+        let classic = start.elapsed().as_micros() / runs as u128;
+        let start = Instant::now();
+        for _ in 0..runs { mod_inv(&x, &n); }
+        let half_gcd = start.elapsed().as_micros() / runs as u128;
+        println!("{:>8} {:>14} {:>14}", bits, classic, half_gcd);
+        if half_gcd >= classic {
+            crossover = None;
+        } else if crossover.is_none() {
+            crossover = Some(bits);
+        }
+    }
+    match crossover {
+        Some(bits) => println!("The half-GCD inversion is faster from {} bits on", bits),
+        None => println!("The half-GCD inversion is not faster for the sizes above"),
+    }
+}
@@ -0,0 +1,256 @@
+/*
+   The Inversion of Polynomials by Means of the Half-GCD Algorithm over the
+                 Goldilocks Field: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// The classic Extended Euclidean Algorithm inverts g modulo f in F_p[X] in
+// O(n^2) field operations for deg f = n. The half-GCD algorithm finds the
+// first half of the quotient sequence from the leading halves of the
+// polynomials recursively, and with the NTT-based multiplication it costs
+// O(n log^2 n). For polynomials there are no carries, so unlike for integers
+// the leading halves determine the quotients exactly. The field is the
+// Goldilocks one, p = 2^64 - 2^32 + 1, which has 2^32-th roots of unity; its
+// arithmetic and the NTT are in Goldilocks.rs.
+// Compile with -O: main runs a benchmark against the classic inversion and
+// prints the crossover point
+use std::time::Instant;
+
+// The Goldilocks field arithmetic and the NTT-based convolution
+#[allow(dead_code)]
+mod goldilocks {
+    include!("Goldilocks.rs");
+}
+
+use goldilocks::*;
+
+// A polynomial over F_p, the coefficient of X^i at index i, without leading
+// zero coefficients (0 is the empty vector)
+type Poly = Vec<u64>;
+// A 2x2 matrix of polynomials ((m_11, m_12), (m_21, m_22))
+type Matrix = [Poly; 4];
+
+// Operands shorter than this are multiplied by the schoolbook method
+const NTT_THRESHOLD: usize = 64;
+// Polynomials of lower degree are reduced by the classic steps only
+const HGCD_THRESHOLD: usize = 64;
+
+fn norm(mut a: Poly) -> Poly {
+    while a.last() == Some(&0) { a.pop(); }
+    a
+}
+
+// The degree; the zero polynomial has degree -1 here
+fn deg(a: &Poly) -> isize {
+    a.len() as isize - 1
+}
+
+fn add(a: &Poly, b: &Poly) -> Poly {
+    norm((0..a.len().max(b.len())).map(|i| add_mod(*a.get(i).unwrap_or(&0), *b.get(i).unwrap_or(&0))).collect())
+}
+
+fn sub(a: &Poly, b: &Poly) -> Poly {
+    norm((0..a.len().max(b.len())).map(|i| sub_mod(*a.get(i).unwrap_or(&0), *b.get(i).unwrap_or(&0))).collect())
+}
+
+// Returns a div X^k, i.e. drops the k lowest coefficients
+fn shr(a: &Poly, k: usize) -> Poly {
+    a.get(k..).map_or(vec![], |top| top.to_vec())
+}
+
+fn mul(a: &Poly, b: &Poly) -> Poly {
+    if a.is_empty() || b.is_empty() { return vec![]; }
+    if a.len().min(b.len()) < NTT_THRESHOLD {
+        let mut r = vec![0; a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() { r[i + j] = add_mod(r[i + j], mul_mod(x, y)); }
+        }
+        return norm(r);
+    }
+    norm(convolve(a, b))
+}
+
+// Returns the quotient and the remainder of a / b for b != 0 by the long
+// division, which costs O((deg a - deg b + 1) * deg b)
+fn divrem(a: &Poly, b: &Poly) -> (Poly, Poly) {
+    assert!(!b.is_empty(), "Division by zero!");
+    if a.len() < b.len() { return (vec![], a.clone()); }
+    let (mut r, lead_inv) = (a.clone(), inv_mod(*b.last().unwrap()));
+    let mut q = vec![0; a.len() - b.len() + 1];
+    for i in (0..q.len()).rev() {
+        let c = mul_mod(r[i + b.len() - 1], lead_inv);
+        q[i] = c;
+        for (j, &y) in b.iter().enumerate() { r[i + j] = sub_mod(r[i + j], mul_mod(c, y)); }
+    }
+    r.truncate(b.len() - 1);
+    (q, norm(r))
+}
+
+fn mat_mul(x: &Matrix, y: &Matrix) -> Matrix {
+    [
+        add(&mul(&x[0], &y[0]), &mul(&x[1], &y[2])),
+        add(&mul(&x[0], &y[1]), &mul(&x[1], &y[3])),
+        add(&mul(&x[2], &y[0]), &mul(&x[3], &y[2])),
+        add(&mul(&x[2], &y[1]), &mul(&x[3], &y[3])),
+    ]
+}
+
+// Returns M * (a, b)
+fn apply(m: &Matrix, a: &Poly, b: &Poly) -> (Poly, Poly) {
+    (add(&mul(&m[0], a), &mul(&m[1], b)), add(&mul(&m[2], a), &mul(&m[3], b)))
+}
+
+fn identity() -> Matrix {
+    [vec![1], vec![], vec![], vec![1]]
+}
+
+// Performs one step of the Euclidean algorithm for (c, d), d != 0: with
+// c = q * d + r, (d, r) = ((0, 1), (1, -q)) * (c, d), so M becomes
+// ((0, 1), (1, -q)) * M
+fn step(m: &mut Matrix, c: &mut Poly, d: &mut Poly) {
+    let (q, r) = divrem(c, d);
+    *c = std::mem::replace(d, r);
+    let [m_11, m_12, m_21, m_22] = std::mem::replace(m, identity());
+    *m = [m_21.clone(), m_22.clone(), sub(&m_11, &mul(&q, &m_21)), sub(&m_12, &mul(&q, &m_22))];
+}
+
+// For deg a = n > deg b returns the matrix M of the quotients of the
+// Euclidean algorithm, such that (c, d) = M * (a, b) are the consecutive
+// remainders with deg c >= m > deg d, m = ceil(n / 2).
+// The correctness rests on the following lemma (see e.g. K. Thull and C. Yap,
+// "A unified approach to HGCD algorithms for polynomials and integers"):
+// if a = a' * X^k + a_0 and b = b' * X^k + b_0 with deg a_0, deg b_0 < k,
+// then the quotients of (a, b) and (a', b') coincide as long as the remainders
+// of (a', b') have degree at least (deg a' + 1) / 2 (rounded up), since each
+// such quotient depends only on the leading coefficients of the remainders,
+// which are not affected by a_0 and b_0. The first recursive call is for
+// k = m, so the remainders of (a', b') stop right above degree
+// ceil((n - m) / 2), i.e. above degree m + ceil((n - m) / 2) for (a, b),
+// which is about 3n / 4. After one more step the second call for the leading
+// part of (c, d) above X^k, where k = 2m - deg c, reduces deg c - k =
+// 2 (deg c - m) to deg c - m, i.e. (c, d) to degree m
+fn hgcd(a: &Poly, b: &Poly) -> Matrix {
+    let m = (a.len() / 2) as isize;
+    let mut r = identity();
+    if deg(b) < m { return r; }
+    let (mut c, mut d);
+    if deg(a) < HGCD_THRESHOLD as isize {
+        (c, d) = (a.clone(), b.clone());
+    } else {
+        r = hgcd(&shr(a, m as usize), &shr(b, m as usize));
+        (c, d) = apply(&r, a, b);
+        if deg(&d) >= m {
+            step(&mut r, &mut c, &mut d);
+            if deg(&d) >= m {
+                let k = (2 * m - deg(&c)) as usize;
+                let s = hgcd(&shr(&c, k), &shr(&d, k));
+                (c, d) = apply(&s, &c, &d);
+                r = mat_mul(&s, &r);
+            }
+        }
+    }
+    // The classic steps for small degrees; for large ones the above reaches
+    // deg d < m by the lemma, and this loop does nothing
+    while deg(&d) >= m { step(&mut r, &mut c, &mut d); }
+    r
+}
+
+// Computes the inverse of g modulo f, deg f >= 1, by applying the half-GCD
+// algorithm. If g and f are not coprime, the inverse does not exist, so None
+// is returned
+fn mod_inv(g: &Poly, f: &Poly) -> Option<Poly> {
+    let (mut m, mut a, mut b) = (identity(), f.clone(), divrem(g, f).1);
+    // (a, b) = M * (f, g); the half-GCD halves the degrees, and the step
+    // after it guarantees progress when the half-GCD cannot make any
+    while !b.is_empty() {
+        let h = hgcd(&a, &b);
+        (a, b) = apply(&h, &a, &b);
+        m = mat_mul(&h, &m);
+        if !b.is_empty() { step(&mut m, &mut a, &mut b); }
+    }
+    // Now a = m_11 * f + m_12 * g is the GCD of f and g up to a constant
+    // factor; if it is a non-zero constant, m_12 / a is the inverse of g
+    if deg(&a) != 0 { return None; }
+    let c = inv_mod(a[0]);
+    Some(norm(m[1].iter().map(|&x| mul_mod(x, c)).collect()))
+}
+
+// The classic Extended Euclidean Algorithm for polynomials, as the classic
+// mod_inv: (s, b) = (x_s * g + n_s * f, x_b * g + n_b * f)
+fn classic_mod_inv(g: &Poly, f: &Poly) -> Option<Poly> {
+    let (mut s, mut x_s, mut b, mut x_b) = (divrem(g, f).1, vec![1], f.clone(), vec![]);
+    while !s.is_empty() {
+        let (q, r) = divrem(&b, &s);
+        let x_next = sub(&x_b, &mul(&q, &x_s));
+        (s, x_s, b, x_b) = (r, x_next, s, x_s);
+    }
+    if deg(&b) != 0 { return None; }
+    let c = inv_mod(b[0]);
+    Some(norm(x_b.iter().map(|&x| mul_mod(x, c)).collect()))
+}
+
+include!("SplitMix64.rs");
+
+// Returns a random polynomial of exactly the given degree
+fn random(degree: usize, state: &mut u64) -> Poly {
+    let mut r: Poly = (0..=degree).map(|_| next_u64(state) % P).collect();
+    r[degree] = r[degree].max(1);
+    r
+}
+
+fn main() {
+    let mut state = 2023;
+    for _ in 0..100_000 {
+        let x = (next_u64(&mut state) as u128) << 64 | next_u64(&mut state) as u128;
+        assert!(reduce(x) as u128 == x % P as u128, "Incorrect reduction!");
+    }
+    let schoolbook = |a: &Poly, b: &Poly| {
+        let mut r = vec![0; a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() { r[i + j] = add_mod(r[i + j], mul_mod(x, y)); }
+        }
+        r
+    };
+    let (a, b) = (random(300, &mut state), random(200, &mut state));
+    assert!(mul(&a, &b) == schoolbook(&a, &b), "Incorrect NTT multiplication!");
+    // Correctness: random pairs of all degrees, coprime or with a common
+    // factor of degree 1..3
+    for i in 0..200 {
+        let n = 1 + 7 * i;
+        let (mut f, mut g) = (random(n, &mut state), random(next_u64(&mut state) as usize % n, &mut state));
+        if i % 4 == 0 {
+            let common = random(1 + i % 3, &mut state);
+            (f, g) = (mul(&f, &common), mul(&g, &common));
+        }
+        let inv = mod_inv(&g, &f);
+        assert!(inv == classic_mod_inv(&g, &f), "Mismatch with the classic inversion!");
+        match inv {
+            Some(inv) => assert!(divrem(&mul(&g, &inv), &f).1 == [1], "Incorrect inverse!"),
+            None => assert!(i % 4 == 0, "No inverse for coprime polynomials!"),
+        }
+    }
+    // Benchmark
+    println!("{:>8} {:>14} {:>14}", "degree", "classic (us)", "half-gcd (us)");
+    let mut crossover = None;
+    for log in 5..=14 {
+        let n = 1 << log;
+        let (f, g) = (random(n, &mut state), random(n - 1, &mut state));
+        let runs = (1 << 15) / n;
+        let start = Instant::now();
+        for _ in 0..runs { classic_mod_inv(&g, &f); }
+        let classic = start.elapsed().as_micros() / runs as u128;
+        let start = Instant::now();
+        for _ in 0..runs { mod_inv(&g, &f); }
+        let half_gcd = start.elapsed().as_micros() / runs as u128;
+        println!("{:>8} {:>14} {:>14}", n, classic, half_gcd);
+        if half_gcd >= classic {
+            crossover = None;
+        } else if crossover.is_none() {
+            crossover = Some(n);
+        }
+    }
+    match crossover {
+        Some(n) => println!("The half-GCD inversion is faster from degree {} on", n),
+        None => println!("The half-GCD inversion is not faster for the degrees above"),
+    }
+}
@@ -0,0 +1,121 @@
+/*
+  The Modular Inversion for Arbitrary (Including Even) Moduli by Means of the
+   Binary Extended Euclidean Algorithm, Hensel Lifting and the Chinese
+               Remainder Theorem: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// The binary mod_inv requires an odd modulus. Any n >= 2 can be written as
+// n = 2^k * m with odd m, and x is invertible modulo n if and only if it is
+// invertible modulo both 2^k and m, since GCD(2^k, m) = 1. The inverse modulo
+// m is computed by the binary algorithm, the inverse modulo 2^k by Newton's
+// iteration (Hensel lifting), and the two are recombined by the Chinese
+// Remainder Theorem. This covers the arithmetic of the EVM, where the modulus
+// of MULMOD or MODEXP is arbitrary
+
+// The binary mod_inv for odd n and 0 <= x < n working on u64 of "Proof and
+// Implementation of Unsigned Binary Euclidean Inversion.rs"
+#[allow(dead_code)]
+mod unsigned {
+    include!("Proof and Implementation of Unsigned Binary Euclidean Inversion.rs");
+
+    pub fn binary_gcd_inv(x: u64, n: u64) -> (u64, u64) {
+        binary_loop(x, n)
+    }
+}
+
+// The inverse of x modulo odd n for 0 <= x < n, which also checks that x and
+// n are coprime: b = GCD(x, n) when the loop ends
+fn odd_mod_inv(x: u64, n: u64) -> Option<u64> {
+    let (b, v) = unsigned::binary_gcd_inv(x, n);
+    if b == 1 { Some(v) } else { None }
+}
+
+// Computes the multiplicative inverse of odd x modulo 2^k for k <= 64. The
+// result is reduced modulo 2^k
+fn pow2_mod_inv(x: u64, k: u32) -> u64 {
+    // Since x is odd, x = 2t + 1 and x^2 = 4t(t + 1) + 1 = 1 (mod 8), as
+    // t(t + 1) is even. Thus, y = x is the inverse of x modulo 2^3
+    let (mut y, mut bits) = (x, 3);
+    // If x * y = 1 - e, where e = 0 (mod 2^j), then the Newton step
+    // y' = y * (2 - x * y) gives x * y' = (1 - e) * (1 + e) = 1 - e^2, and
+    // e^2 = 0 (mod 2^(2j)). Thus, each step doubles the number of correct
+    // low bits. All the computations are modulo 2^64, i.e. wrapping, which
+    // does not break the congruences modulo 2^j for j <= 64
+    while bits < k {
+        y = y.wrapping_mul(2u64.wrapping_sub(x.wrapping_mul(y)));
+        bits *= 2;
+    }
+    if k < 64 { y & ((1 << k) - 1) } else { y }
+}
+
+// Computes the multiplicative inverse of x modulo n for any n >= 2; panics in
+// the case of n < 2. If x and n are not coprime, the inverse does not exist,
+// so None is returned
+fn mod_inv(x: u64, n: u64) -> Option<u64> {
+    if n < 2 { panic!("The modulus must be greater than 1!"); }
+    let x = x % n;
+    let k = n.trailing_zeros();
+    let m = n >> k;
+    // The inverse modulo the odd part m; for m = 1 every residue is 0
+    let r_m = if m > 1 { odd_mod_inv(x % m, m)? } else { 0 };
+    if k == 0 { return Some(r_m); }
+    // x is invertible modulo 2^k with k > 0 if and only if it is odd
+    if x & 1 == 0 { return None; }
+    let r_2 = pow2_mod_inv(x, k);
+    // We seek r = r_m + m * t, which is r_m modulo m for any t. It is r_2
+    // modulo 2^k if t = (r_2 - r_m) * m^(-1) mod 2^k; m is odd, so m^(-1)
+    // modulo 2^k exists. Since 0 <= r_m < m and 0 <= t < 2^k, we have
+    // 0 <= r <= m - 1 + m * (2^k - 1) = n - 1, so r is reduced modulo n and
+    // nothing overflows. By the Chinese Remainder Theorem r * x = 1 modulo
+    // both m and 2^k implies r * x = 1 (mod n)
+    let mask = if k < 64 { (1 << k) - 1 } else { u64::MAX };
+    let t = r_2.wrapping_sub(r_m).wrapping_mul(pow2_mod_inv(m, k)) & mask;
+    Some(r_m + m * t)
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b > 0 { (a, b) = (b, a % b); }
+    a
+}
+
+include!("SplitMix64.rs");
+
+fn check(x: u64, n: u64) {
+    match mod_inv(x, n) {
+        Some(i) => {
+            assert!(i < n, "The inverse of {} mod {} is not reduced!", x, n);
+            assert!((i as u128 * x as u128) % n as u128 == 1, "Incorrect inverse of {} mod {}!", x, n);
+        }
+        None => assert!(gcd(x, n) != 1, "No inverse of {} mod {}!", x, n),
+    }
+}
+
+fn main() {
+    // Every pair with a small modulus, including powers of two
+    for n in 2..1 << 10 {
+        for x in 0..n { check(x, n); }
+    }
+    // The inverses modulo 2^k for all odd 16-bit values and all k
+    for x in (1..1 << 16).step_by(2) {
+        for k in 1..=64 {
+            let y = pow2_mod_inv(x, k);
+            assert!(x.wrapping_mul(y) & (u64::MAX >> (64 - k)) == 1, "Incorrect inverse mod 2^{}!", k);
+        }
+    }
+    // Random pairs with random moduli (half of them even), moduli with many
+    // factors of two and the largest powers of two
+    let mut state = 2023;
+    for _ in 0..100_000 {
+        let (x, n) = (next_u64(&mut state), next_u64(&mut state));
+        let shift = next_u64(&mut state) % 64;
+        if n >= 2 { check(x, n); }
+        if n >> shift >= 2 { check(x, n >> shift << shift); }
+        check(x, 1 << (63 - shift % 63));
+    }
+    let (x, n) = (3, 10);
+    match mod_inv(x, n) {
+        Some(r) => println!("{}", r),
+        None => println!("{} and {} are not coprime!", x, n),
+    }
+}
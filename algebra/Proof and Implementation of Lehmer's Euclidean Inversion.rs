@@ -0,0 +1,219 @@
+/*
+  The Multi-Limb Modular Inversion by Means of Lehmer's Extended Euclidean
+                  Algorithm: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// The classic mod_inv performs one full-precision division per quotient. For
+// 256-bit numbers most quotients are small, and they are determined by the
+// leading bits of s and b only. Lehmer's algorithm (in the form of Knuth,
+// TAOCP vol. 2, 4.5.2, Algorithm L) computes as many quotients as possible
+// from the leading 64 bits in single precision, accumulates them in a 2x2
+// cofactor matrix and applies the matrix to the full numbers at once
+
+// Unsigned integers stored as 64-bit limbs, the least significant limb first
+type U256 = [u64; 4];
+type U320 = [u64; 5];
+
+const ZERO: U256 = [0, 0, 0, 0];
+const ONE: U256 = [1, 0, 0, 0];
+
+// The BN254 base field modulus q and scalar field modulus r
+const BN254_Q: U256 = [
+    0x3c208c16d87cfd47, 0x97816a916871ca8d, 0xb85045b68181585d, 0x30644e72e131a029,
+];
+const BN254_R: U256 = [
+    0x43e1f593f0000001, 0x2833e84879b97091, 0xb85045b68181585d, 0x30644e72e131a029,
+];
+// The secp256k1 base field modulus 2^256 - 2^32 - 977, which uses all 256 bits
+const SECP256K1_P: U256 = [
+    0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff,
+];
+
+// The arithmetic of "Proof and Implementation of Multi-Limb Binary Euclidean
+// Inversion.rs"; its binary mod_inv is the reference in the tests
+#[allow(dead_code)]
+mod limbs {
+    include!("MultiLimb.rs");
+}
+
+use limbs::*;
+
+// Returns bits [k, k + 64) of a, i.e. the lowest limb of a >> k
+fn word_at(a: &U256, k: usize) -> u64 {
+    let (i, j) = (k / 64, k % 64);
+    let lo = a[i] >> j;
+    let hi = if j > 0 && i < 3 { a[i + 1] << (64 - j) } else { 0 };
+    lo | hi
+}
+
+// Returns a * m, which needs one more limb
+fn mul_small(a: &U256, m: u64) -> U320 {
+    let (mut r, mut carry) = ([0; 5], 0u128);
+    for i in 0..4 {
+        let t = a[i] as u128 * m as u128 + carry;
+        (r[i], carry) = (t as u64, t >> 64);
+    }
+    r[4] = carry as u64;
+    r
+}
+
+// Returns the lowest four limbs of a, which must hold the whole value
+fn truncate(a: &U320) -> U256 {
+    assert!(a[4] == 0, "The value does not fit in 256 bits!");
+    [a[0], a[1], a[2], a[3]]
+}
+
+// Returns a * b mod 2^256
+fn mul_low(a: &U256, b: &U256) -> U256 {
+    let mut r = [0u64; 4];
+    for i in 0..4 {
+        let mut carry = 0u128;
+        for j in 0..4 - i {
+            let t = r[i + j] as u128 + a[i] as u128 * b[j] as u128 + carry;
+            (r[i + j], carry) = (t as u64, t >> 64);
+        }
+    }
+    r
+}
+
+// Returns the quotient and the remainder of a / b for b > 0 by the bitwise
+// long division; this is the full-precision step Lehmer's algorithm avoids
+fn div_rem(a: &U256, b: &U256) -> (U256, U256) {
+    let (mut q, mut r) = (ZERO, ZERO);
+    for i in (0..bits(a)).rev() {
+        // r < b, so 2r + 1 < 2b may need the carry bit
+        let top = r[3] >> 63;
+        for j in (1..4).rev() { r[j] = r[j] << 1 | r[j - 1] >> 63; }
+        r[0] = r[0] << 1 | bit(a, i) as u64;
+        if top == 1 || ge(&r, b) {
+            r = sub(&r, b).0;
+            q[i / 64] |= 1 << (i % 64);
+        }
+    }
+    (q, r)
+}
+
+// Returns |p * a - q * c| for the absolute values p and q of the cofactors,
+// when the value is known to be less than 2^256
+fn abs_diff(a: &U256, p: u64, c: &U256, q: u64) -> U256 {
+    let (x, y) = (mul_small(a, p), mul_small(c, q));
+    truncate(&if ge(&x, &y) { sub(&x, &y).0 } else { sub(&y, &x).0 })
+}
+
+// Returns p * a + q * c, when the value is known to be less than 2^256
+fn sum(a: &U256, p: u64, c: &U256, q: u64) -> U256 {
+    truncate(&add(&mul_small(a, p), &mul_small(c, q)).0)
+}
+
+// Computes the multiplicative inverse of x modulo n by applying Lehmer's
+// Extended Euclidean Algorithm to 256-bit numbers for n > 0. If x and n are
+// not coprime, the inverse does not exist, so None is returned
+fn mod_inv(x: &U256, n: &U256) -> Option<U256> {
+    // Working not with x, but with such x' that 0 <= x' < n and x = x' (mod n),
+    // which costs one full-precision division for x >= n
+    let x = if ge(x, n) { div_rem(x, n).1 } else { *x };
+    let (mut s, mut x_s, mut b, mut x_b, mut s_neg) = (x, ONE, *n, ZERO, false);
+    // As in the classic mod_inv, s = x_s * x' + n_s * n and
+    // b = x_b * x' + n_b * n, where the values of n_s and n_b are not needed.
+    // The coefficients are stored as their absolute values; as shown for the
+    // classic mod_inv working on unsigned values ("Proof and Implementation
+    // of Generic Modular Inversion.rs"), x_s and x_b have opposite signs, x_s
+    // changing its sign with every quotient, and their absolute values do not
+    // exceed n
+    while !is_zero(&s) {
+        // We take the 64 leading bits of b and the bits of s in the same
+        // positions: b = b_h * 2^k + e_b and s = s_h * 2^k + e_s, where
+        // 0 <= e_b, e_s < 2^k. If b < 2^64, then k = 0 and the values are exact
+        let k = bits(&b).saturating_sub(64);
+        let (mut b_h, mut s_h) = (word_at(&b, k) as i128, word_at(&s, k) as i128);
+        // The cofactor matrix ((p, q), (r, t)) expresses the values after the
+        // quotients computed so far: b' = p * b + q * s and s' = r * b + t * s,
+        // and the same holds true for the coefficients: x_b' = p * x_b + q * x_s
+        // and x_s' = r * x_b + t * x_s. It is the product of the matrices
+        // ((0, 1), (1, -q_i)), so p and q, as well as r and t, have opposite
+        // signs (or one of them is 0), and |p|, |q|, |r|, |t| <= b_h < 2^64.
+        // Since b_h and s_h are transformed by the same matrix, b_h + p,
+        // b_h + q, s_h + r and s_h + t are the leading parts of the bounds
+        // of b' and s': as e_b and e_s range over [0, 2^k), b' / 2^k lies
+        // between b_h + min(p, q) and b_h + max(p, q), and s' / 2^k between
+        // s_h + min(r, t) and s_h + max(r, t), where p and r, as well as q
+        // and t, have opposite signs. Thus, the true quotient b' / s' lies
+        // between (b_h + p) / (s_h + r) and (b_h + q) / (s_h + t), and when
+        // their integer parts are equal, this is the integer part of the true
+        // quotient (Knuth, ibid.)
+        let (mut p, mut q, mut r, mut t, mut steps) = (1i128, 0i128, 0i128, 1i128, 0u32);
+        while s_h + r != 0 && s_h + t != 0 {
+            let quotient = (b_h + p) / (s_h + r);
+            if quotient != (b_h + q) / (s_h + t) { break; }
+            (p, q, r, t) = (r, t, p - quotient * r, q - quotient * t);
+            (b_h, s_h) = (s_h, b_h - quotient * s_h);
+            steps += 1;
+        }
+        if steps == 0 {
+            // No quotient could be determined from the leading bits, which
+            // happens when it is large, so we perform one full-precision
+            // step, as the classic mod_inv does. The new |x_s| is
+            // |x_b| + quotient * |x_s| <= n, so the product does not overflow
+            let (quotient, rem) = div_rem(&b, &s);
+            (s, x_s, b, x_b) = (rem, add(&x_b, &mul_low(&quotient, &x_s)).0, s, x_s);
+            s_neg = !s_neg;
+        } else {
+            // b' = p * b + q * s and s' = r * b + t * s are non-negative
+            // remainders, and p * b and q * s have opposite signs, so
+            // b' = | |p| * b - |q| * s |; the same holds true for s'. Since
+            // x_b and x_s have opposite signs as well as p and q, the products
+            // p * x_b and q * x_s have the same sign, so
+            // |x_b'| = |p| * |x_b| + |q| * |x_s|, and the same for x_s'.
+            // The new coefficients alternate in sign as before, x_s having
+            // changed its sign steps times
+            let abs = |v: i128| v.unsigned_abs() as u64;
+            (b, s) = (abs_diff(&b, abs(p), &s, abs(q)), abs_diff(&b, abs(r), &s, abs(t)));
+            (x_b, x_s) = (sum(&x_b, abs(p), &x_s, abs(q)), sum(&x_b, abs(r), &x_s, abs(t)));
+            s_neg ^= steps & 1 == 1;
+        }
+    }
+    // Now b = GCD(x', n). If b = 1, then x_b * x = 1 (mod n), and x_b is
+    // negative if and only if x_s is positive and x_b is not 0
+    if b != ONE { return None; }
+    Some(if !s_neg && !is_zero(&x_b) { sub(n, &x_b).0 } else { x_b })
+}
+
+include!("SplitMix64.rs");
+
+fn main() {
+    let mut state = 2023;
+    for (name, n) in [("BN254 q", BN254_Q), ("BN254 r", BN254_R), ("secp256k1 p", SECP256K1_P)] {
+        // Random values of all the sizes, so that both the single-precision
+        // and the full-precision steps are exercised
+        for i in 0..2_000 {
+            let mut x = [next_u64(&mut state), next_u64(&mut state), next_u64(&mut state), next_u64(&mut state)];
+            let limbs = i % 4 + 1;
+            x[limbs..].fill(0);
+            x[limbs - 1] >>= next_u64(&mut state) % 64;
+            // x is not reduced: for 4 limbs it is mostly above n
+            let x_reduced = div_rem(&x, &n).1;
+            if is_zero(&x_reduced) { continue; }
+            let inv = mod_inv(&x, &n).expect("The modulus is prime!");
+            assert!(!ge(&inv, &n), "The inverse is not reduced modulo {}!", name);
+            assert!(mul_mod(&x_reduced, &inv, &n) == ONE, "Incorrect inverse modulo {}!", name);
+            assert!(limbs::mod_inv(&x, &n) == Ok(inv), "Mismatch with the binary mod_inv modulo {}!", name);
+        }
+        let minus_one = sub(&n, &ONE).0;
+        assert!(mod_inv(&minus_one, &n) == Some(minus_one), "Incorrect inverse modulo {}!", name);
+        assert!(mod_inv(&ZERO, &n).is_none(), "Zero is not invertible modulo {}!", name);
+        // x >= n: n itself, n + 2 and 2^256 - 1, which is n + (2^256 - 1 - n)
+        assert!(mod_inv(&n, &n).is_none(), "Zero is not invertible modulo {}!", name);
+        let two = [2, 0, 0, 0];
+        assert!(mod_inv(&add(&n, &two).0, &n) == mod_inv(&two, &n), "Incorrect inverse modulo {}!", name);
+        let max = [u64::MAX; 4];
+        assert!(mod_inv(&max, &n) == mod_inv(&sub(&max, &n).0, &n), "Incorrect inverse modulo {}!", name);
+        println!("{}: OK", name);
+    }
+    // A composite modulus: 2^256 - 1 is divisible by 3
+    let n = [u64::MAX; 4];
+    assert!(mod_inv(&[3, 0, 0, 0], &n).is_none(), "3 is not invertible!");
+    let x = [7, 0, 0, 1];
+    let inv = mod_inv(&x, &n).expect("7 + 2^192 is coprime with 2^256 - 1!");
+    assert!(mul_mod(&x, &inv, &n) == ONE, "Incorrect inverse!");
+}
@@ -0,0 +1,206 @@
+/*
+   The Modular Square Roots in Prime Fields by Means of the Tonelli-Shanks and
+             Cipolla Algorithms: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// The decompression of points and hash-to-curve need square roots in prime
+// fields. For p = 3 (mod 4) the root of a square a is a^((p + 1) / 4), but
+// the scalar fields of BN254 and BLS12-381 and the Goldilocks field have a
+// large 2-adicity s, the exponent of 2 in p - 1. Tonelli-Shanks works for any
+// p with one exponentiation and O(s^2) multiplications, using the 2-adic data
+// of p, which is computed once. Cipolla works with about three times as many
+// multiplications as one exponentiation, but independently of s, so it is the
+// fallback for the fields with a very large 2-adicity
+
+// A 384-bit unsigned integer stored as six 64-bit limbs, the least
+// significant limb first. This is enough for the BLS12-381 base field
+// modulus (381 bits) and for all the smaller moduli
+type U384 = [u64; 6];
+
+const ZERO: U384 = [0; 6];
+const ONE: U384 = [1, 0, 0, 0, 0, 0];
+
+// The BN254 base field modulus q and scalar field modulus r
+const BN254_Q: U384 = [0x3c208c16d87cfd47, 0x97816a916871ca8d, 0xb85045b68181585d, 0x30644e72e131a029, 0, 0];
+const BN254_R: U384 = [0x43e1f593f0000001, 0x2833e84879b97091, 0xb85045b68181585d, 0x30644e72e131a029, 0, 0];
+// The BLS12-381 base field modulus p and scalar field modulus r
+const BLS12_381_P: U384 = [
+    0xb9feffffffffaaab, 0x1eabfffeb153ffff, 0x6730d2a0f6b0f624,
+    0x64774b84f38512bf, 0x4b1ba7b6434bacd7, 0x1a0111ea397fe69a,
+];
+const BLS12_381_R: U384 = [0xffffffff00000001, 0x53bda402fffe5bfe, 0x3339d80809a1d805, 0x73eda753299d7d48, 0, 0];
+// The Goldilocks modulus 2^64 - 2^32 + 1
+const GOLDILOCKS: U384 = [0xffffffff00000001, 0, 0, 0, 0, 0];
+
+// The arithmetic and the binary mod_inv of "Proof and Implementation of
+// Multi-Limb Binary Euclidean Inversion.rs" for six limbs
+#[allow(dead_code)]
+mod limbs {
+    include!("MultiLimb.rs");
+}
+
+use limbs::*;
+
+// The Legendre symbol (a / p) by Euler's criterion: a^((p - 1) / 2) is 1 for
+// the non-zero squares and -1 for the non-squares modulo an odd prime p
+fn legendre(a: &U384, p: &U384) -> i32 {
+    let e = pow_mod(a, &shr1(&sub(p, &ONE).0, false), p);
+    if is_zero(&e) { 0 } else if e == ONE { 1 } else { -1 }
+}
+
+// The data of an odd prime p, which the square roots modulo p need: the
+// 2-adic decomposition p - 1 = 2^s * q with odd q, and the power c = z^q of
+// the smallest non-square z, which is computed once for each field
+struct SqrtParams {
+    p: U384,
+    s: u32,
+    // (q - 1) / 2
+    q_half: U384,
+    // c = z^q, whose order is exactly 2^s: c^(2^(s - 1)) = z^((p - 1) / 2)
+    // = -1 by Euler's criterion
+    c: U384,
+}
+
+impl SqrtParams {
+    // Computes the data for an odd prime p; the primality is not checked
+    fn new(p: &U384) -> SqrtParams {
+        let (mut q, mut s) = (sub(p, &ONE).0, 0);
+        while !is_odd(&q) { (q, s) = (shr1(&q, false), s + 1); }
+        let mut z = [2, 0, 0, 0, 0, 0];
+        while legendre(&z, p) != -1 { z = add(&z, &ONE).0; }
+        SqrtParams { p: *p, s, q_half: shr1(&q, false), c: pow_mod(&z, &q, p) }
+    }
+}
+
+// Computes a square root of a < p modulo p by applying the Tonelli-Shanks
+// algorithm. If a is not a square, None is returned
+fn tonelli_shanks(a: &U384, params: &SqrtParams) -> Option<U384> {
+    let p = &params.p;
+    if is_zero(a) { return Some(ZERO); }
+    // w = a^((q - 1) / 2), x = a * w = a^((q + 1) / 2) and t = x * w = a^q
+    let w = pow_mod(a, &params.q_half, p);
+    let x = mul_mod(a, &w, p);
+    let (mut x, mut t, mut c, mut m) = (x, mul_mod(&x, &w, p), params.c, params.s);
+    // Now we have
+    // (1) x^2 = a * t; (2) the order of c is 2^m; (3) t^(2^m) = 1, since
+    // t^(2^s) = a^(p - 1) = 1 by Fermat's little theorem. If a is a square,
+    // then also (4) t^(2^(m - 1)) = 1, since t^(2^(s - 1)) = a^((p - 1) / 2)
+    // = 1 by Euler's criterion; otherwise t^(2^(s - 1)) = -1.
+    // When t = 1, x is the root due to (1). Otherwise, the order of t is 2^i
+    // for some 0 < i <= m due to (3), and i < m, if and only if (4) holds
+    loop {
+        if t == ONE { return Some(x); }
+        let (mut i, mut t2) = (1, mul_mod(&t, &t, p));
+        while t2 != ONE { (i, t2) = (i + 1, mul_mod(&t2, &t2, p)); }
+        // The order of t is 2^m, so (4) does not hold, and a is not a square.
+        // This happens only in the first iteration, where m = s
+        if i == m { return None; }
+        // Let b = c^(2^(m - i - 1)), so that the order of b^2 is 2^i due to
+        // (2) and (b^2)^(2^(i - 1)) = c^(2^(m - 1)) = -1 = t^(2^(i - 1)).
+        // Then (t * b^2)^(2^(i - 1)) = 1, so with x' = x * b, t' = t * b^2,
+        // c' = b^2 and m' = i we have (1)-(4) again, and m decreases
+        let mut b = c;
+        for _ in 0..m - i - 1 { b = mul_mod(&b, &b, p); }
+        x = mul_mod(&x, &b, p);
+        c = mul_mod(&b, &b, p);
+        t = mul_mod(&t, &c, p);
+        m = i;
+    }
+}
+
+// Computes a square root of a < p modulo an odd prime p by applying
+// Cipolla's algorithm. If a is not a square, None is returned
+fn cipolla(a: &U384, p: &U384) -> Option<U384> {
+    if is_zero(a) { return Some(ZERO); }
+    if legendre(a, p) != 1 { return None; }
+    // Find r such that d = r^2 - a is not a square; about a half of all r
+    // are suitable. Then F_p[w] / (w^2 - d) is the field of p^2 elements
+    let (mut r, mut d) = (ZERO, sub_mod(&ZERO, a, p));
+    while legendre(&d, p) != -1 {
+        r = add(&r, &ONE).0;
+        d = sub_mod(&mul_mod(&r, &r, p), a, p);
+    }
+    // (x_0 + x_1 * w) * (y_0 + y_1 * w) = (x_0 * y_0 + x_1 * y_1 * d) +
+    // (x_0 * y_1 + x_1 * y_0) * w
+    let mul = |x: &(U384, U384), y: &(U384, U384)| {
+        let t = mul_mod(&mul_mod(&x.1, &y.1, p), &d, p);
+        (add_mod(&mul_mod(&x.0, &y.0, p), &t, p), add_mod(&mul_mod(&x.0, &y.1, p), &mul_mod(&x.1, &y.0, p), p))
+    };
+    // We have w^p = w * d^((p - 1) / 2) = -w, since d is not a square, and
+    // the Frobenius map y -> y^p is additive, so (r + w)^(p + 1) =
+    // (r - w) * (r + w) = r^2 - d = a. Thus, y = (r + w)^((p + 1) / 2)
+    // satisfies y^2 = a. Let y = y_0 + y_1 * w; then y^2 = y_0^2 + y_1^2 * d +
+    // 2 * y_0 * y_1 * w = a, so y_0 * y_1 = 0. If y_0 = 0, then
+    // d = a * (y_1^(-1))^2 is a square, which is not true; thus, y_1 = 0 and
+    // y_0 is the root in F_p. Since p < 2^381, p + 1 does not overflow
+    let e = shr1(&add(p, &ONE).0, false);
+    let (mut y, base) = ((ONE, ZERO), (r, ONE));
+    for i in (0..bits(&e)).rev() {
+        y = mul(&y, &y);
+        if bit(&e, i) { y = mul(&y, &base); }
+    }
+    Some(y.0)
+}
+
+// Computes a square root of a < p modulo p by Tonelli-Shanks, which needs
+// s (s - 1) / 4 multiplications on average besides the exponentiation, or by
+// Cipolla, which needs about two extra exponentiations, if that is fewer.
+// If a is not a square, None is returned
+fn sqrt(a: &U384, params: &SqrtParams) -> Option<U384> {
+    let s = params.s as usize;
+    if s * (s - 1) / 4 <= 3 * bits(&params.p) { tonelli_shanks(a, params) } else { cipolla(a, &params.p) }
+}
+
+// Computes a square root of u / v modulo p for 0 <= u < p and 0 < v < p,
+// as needed for the decompression of twisted Edwards points, where
+// x^2 = (y^2 - 1) / (d * y^2 + 1). If u / v is not a square, None is
+// returned, and so it is for v = 0, which mod_inv rejects
+fn sqrt_ratio(u: &U384, v: &U384, params: &SqrtParams) -> Option<U384> {
+    let v_inv = mod_inv(v, &params.p).ok()?;
+    sqrt(&mul_mod(u, &v_inv, &params.p), params)
+}
+
+fn main() {
+    let mut state = 2023;
+    let fields = [
+        ("BN254 q", BN254_Q, 1),
+        ("BN254 r", BN254_R, 28),
+        ("BLS12-381 p", BLS12_381_P, 1),
+        ("BLS12-381 r", BLS12_381_R, 32),
+        ("Goldilocks", GOLDILOCKS, 32),
+    ];
+    for (name, p, s) in fields {
+        let params = SqrtParams::new(&p);
+        assert!(params.s == s, "Incorrect 2-adicity of {}!", name);
+        // -1 is a square if and only if p = 1 (mod 4)
+        let minus_one = sub(&p, &ONE).0;
+        assert!(sqrt(&minus_one, &params).is_some() == (s > 1), "Incorrect root of -1 modulo {}!", name);
+        assert!(tonelli_shanks(&ZERO, &params) == Some(ZERO) && cipolla(&ZERO, &p) == Some(ZERO), "Incorrect root!");
+        for _ in 0..20 {
+            let x = random_below(&p, &mut state);
+            let a = mul_mod(&x, &x, &p);
+            let minus_x = sub(&p, &x).0;
+            // Both algorithms return one of the two roots x and -x
+            for r in [tonelli_shanks(&a, &params), cipolla(&a, &p), sqrt(&a, &params)] {
+                assert!(r == Some(x) || r == Some(minus_x), "Incorrect square root modulo {}!", name);
+            }
+            // c * x^2 is not a square, since c^((p - 1) / 2) = (c^(2^(s - 1)))^q
+            // = (-1)^q = -1 for odd q
+            let b = mul_mod(&a, &params.c, &p);
+            assert!(tonelli_shanks(&b, &params).is_none() && cipolla(&b, &p).is_none(), "A root of a non-square!");
+            // u / v = x^2
+            let v = random_below(&p, &mut state);
+            let u = mul_mod(&a, &v, &p);
+            let r = sqrt_ratio(&u, &v, &params).unwrap();
+            assert!(mul_mod(&mul_mod(&r, &r, &p), &v, &p) == u, "Incorrect square root of the ratio modulo {}!", name);
+            assert!(sqrt_ratio(&u, &ZERO, &params).is_none(), "A root of a ratio with v = 0!");
+        }
+        println!("{}: OK", name);
+    }
+    // The decompression of a point of the BN254 curve y^2 = x^3 + 3: x = 1
+    // gives y = +-2
+    let params = SqrtParams::new(&BN254_Q);
+    let y = sqrt(&[4, 0, 0, 0, 0, 0], &params).unwrap();
+    assert!(y == [2, 0, 0, 0, 0, 0] || y == sub(&BN254_Q, &[2, 0, 0, 0, 0, 0]).0, "Incorrect point!");
+}
@@ -0,0 +1,103 @@
+/*
+   The Computation of the Montgomery Constant n' = -n^(-1) mod 2^w by Means
+           of Newton's Iteration: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// Montgomery multiplication with R = 2^w needs n' = -n^(-1) mod 2^w, where n
+// is the odd modulus and w is the word size (for multi-limb moduli only the
+// lowest limb of n matters, since n' is a value modulo 2^w). Both mod_inv
+// implementations require an odd modulus, so they cannot invert modulo 2^w.
+// Instead, we use Newton's iteration, which works modulo powers of two and
+// does not branch on the input; the one modulo 2^64 is shared with the
+// inversion modulo composite numbers
+
+// The lowest limbs of the BN254 base field modulus q and scalar field
+// modulus r together with the known values of their Montgomery constants
+const BN254_Q0: u64 = 0x3c208c16d87cfd47;
+const BN254_Q_INV: u64 = 0x87d20782e4866389;
+const BN254_R0: u64 = 0x43e1f593f0000001;
+const BN254_R_INV: u64 = 0xc2e1f593efffffff;
+
+// Newton's iteration modulo 2^k together with its proof is pow2_mod_inv of
+// "Proof and Implementation of Inversion Modulo Composite Numbers.rs", which
+// is included verbatim
+#[allow(dead_code)]
+mod composite {
+    include!("Proof and Implementation of Inversion Modulo Composite Numbers.rs");
+
+    // Computes the multiplicative inverse of odd x modulo 2^w for
+    // 1 <= w <= 64. The result is reduced modulo 2^w
+    pub fn inv_pow2(x: u64, w: u32) -> u64 {
+        pow2_mod_inv(x, w)
+    }
+}
+
+use composite::inv_pow2;
+
+// Computes the multiplicative inverse of odd x modulo 2^w for 1 <= w <= 128.
+// The same proof as for pow2_mod_inv applies, the computations being modulo
+// 2^128; the inverse modulo 2^64 is lifted with one more step
+fn inv_pow2_u128(x: u128, w: u32) -> u128 {
+    let (mut y, mut bits) = (inv_pow2(x as u64, w.min(64)) as u128, w.min(64));
+    while bits < w {
+        y = y.wrapping_mul(2u128.wrapping_sub(x.wrapping_mul(y)));
+        bits *= 2;
+    }
+    if w < 128 { y & ((1 << w) - 1) } else { y }
+}
+
+// Computes the Montgomery constant n' = -n^(-1) mod 2^64 for odd n, so that
+// n * n' = -1 (mod 2^64)
+fn mont_constant(n: u64) -> u64 {
+    inv_pow2(n, 64).wrapping_neg()
+}
+
+// Computes the Montgomery constant n' = -n^(-1) mod 2^128 for odd n
+fn mont_constant_u128(n: u128) -> u128 {
+    inv_pow2_u128(n, 128).wrapping_neg()
+}
+
+// Computes t * 2^(-64) mod n for odd n < 2^63 and t < n * 2^64 by Montgomery
+// reduction. With m = t * n' mod 2^64 we have t + m * n = t - t = 0
+// (mod 2^64), so the division by 2^64 is exact, and the quotient is
+// t * 2^(-64) modulo n. Since t + m * n < 2n * 2^64 <= 2^128, nothing
+// overflows, and one conditional subtraction reduces the quotient below n
+fn redc(t: u128, n: u64, n_prime: u64) -> u64 {
+    let m = (t as u64).wrapping_mul(n_prime);
+    let r = ((t + m as u128 * n as u128) >> 64) as u64;
+    if r >= n { r - n } else { r }
+}
+
+include!("SplitMix64.rs");
+
+fn main() {
+    // All odd 16-bit inputs for all the widths
+    for x in (1..1u64 << 16).step_by(2) {
+        for w in 1..=64 {
+            let y = inv_pow2(x, w);
+            assert!(y >> (w - 1) >> 1 == 0, "The inverse is not reduced modulo 2^{}!", w);
+            assert!(x.wrapping_mul(y) & (u64::MAX >> (64 - w)) == 1, "Incorrect inverse modulo 2^{}!", w);
+        }
+        for w in 1..=128 {
+            let y = inv_pow2_u128(x as u128, w);
+            assert!((x as u128).wrapping_mul(y) & (u128::MAX >> (128 - w)) == 1, "Incorrect inverse modulo 2^{}!", w);
+        }
+        assert!(x.wrapping_mul(mont_constant(x)) == u64::MAX, "Incorrect constant!");
+        assert!((x as u128).wrapping_mul(mont_constant_u128(x as u128)) == u128::MAX, "Incorrect constant!");
+    }
+    assert!(mont_constant(BN254_Q0) == BN254_Q_INV, "Incorrect constant for BN254 q!");
+    assert!(mont_constant(BN254_R0) == BN254_R_INV, "Incorrect constant for BN254 r!");
+    // Random odd moduli: the reduction of a * b must give a * b * 2^(-64)
+    let mut state = 2023;
+    for _ in 0..100_000 {
+        let n = (next_u64(&mut state) >> 1) | 1;
+        let wide = ((next_u64(&mut state) as u128) << 64 | next_u64(&mut state) as u128) | 1;
+        assert!(wide.wrapping_mul(mont_constant_u128(wide)) == u128::MAX, "Incorrect constant!");
+        let (a, b) = (next_u64(&mut state) % n, next_u64(&mut state) % n);
+        let r = redc(a as u128 * b as u128, n, mont_constant(n));
+        let r_mod_n = ((1u128 << 64) % n as u128) as u64;
+        assert!((r as u128 * r_mod_n as u128) % n as u128 == (a as u128 * b as u128) % n as u128, "Incorrect reduction!");
+    }
+    println!("{:#x}", mont_constant(BN254_Q0));
+}
@@ -0,0 +1,91 @@
+/*
+  The Modular Inversion for the Numbers in the Montgomery Form by Means of the
+       Binary Extended Euclidean Algorithm: Implementation in Rust
+
+                               October 2026
+*/
+// This file implements the method proven in "Proof of Euclidean Inversion for
+// the Montgomery representation.pdf" for the 256-bit binary mod_inv from
+// "Proof and Implementation of Multi-Limb Binary Euclidean Inversion.rs".
+// The Montgomery form of y modulo N is m = y * R mod N, where R = 2^256. By
+// the theorem of the PDF, initializing u with r mod N instead of 1 makes the
+// algorithm return the inverse of m * r^(-1) modulo N; for r = R^2 this is
+// (y * R^(-1))^(-1) = y^(-1) * R mod N, i.e. the Montgomery form of y^(-1).
+// Thus, field elements stored in the Montgomery form never leave it
+
+// A 256-bit unsigned integer stored as four 64-bit limbs, the least
+// significant limb first. This is enough for the BN254 base and scalar field
+// moduli (254 bits) as well as for any other modulus below 2^256
+type U256 = [u64; 4];
+
+const ONE: U256 = [1, 0, 0, 0];
+
+// The BN254 base field modulus q and scalar field modulus r
+const BN254_Q: U256 = [
+    0x3c208c16d87cfd47, 0x97816a916871ca8d, 0xb85045b68181585d, 0x30644e72e131a029,
+];
+const BN254_R: U256 = [
+    0x43e1f593f0000001, 0x2833e84879b97091, 0xb85045b68181585d, 0x30644e72e131a029,
+];
+// The secp256k1 base field modulus 2^256 - 2^32 - 977, which uses all 256 bits
+const SECP256K1_P: U256 = [
+    0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff,
+];
+
+// The arithmetic and the binary loop of "Proof and Implementation of
+// Multi-Limb Binary Euclidean Inversion.rs" for four limbs
+#[allow(dead_code)]
+mod limbs {
+    include!("MultiLimb.rs");
+}
+
+use limbs::*;
+
+// Computes 2^k mod n by doubling 1 modulo n k times. R mod n = 2^256 mod n
+// and R^2 mod n = 2^512 mod n are constants of the field, which are computed
+// once per modulus
+fn pow2_mod(k: usize, n: &U256) -> U256 {
+    let mut r = ONE;
+    for _ in 0..k { r = add_mod(&r, &r, n); }
+    r
+}
+
+// Computes the Montgomery form of the multiplicative inverse of y modulo n,
+// given the Montgomery form m = y * R mod n of y and r2 = R^2 mod n. For
+// applying this method n must be odd, m and n must be coprime, both m and n
+// must be positive
+fn mont_inv(m: &U256, n: &U256, r2: &U256) -> U256 {
+    // We run the loop of the 256-bit binary mod_inv for a = m and b = n with
+    // u = r2 instead of 1. It satisfies the lemma of the PDF: it keeps
+    // (1) GCD(a, b) = GCD(m, n) = 1, (2) a = u * m' (mod n),
+    // (3) b = v * m' (mod n), where m' = m * r2^(-1) mod n, and (4) a and b
+    // are non-negative, decreasing a + b until a is 0, and then returns v.
+    // These are (3) and (4) of binary_loop with y = r2, multiplied by
+    // r2^(-1): before the loop (2) holds, since a = m = r2 * m' (mod n), and
+    // (3) holds, since b = n = 0 (mod n). Also u = r2 < n, so the invariant
+    // u < n and v < n of binary_loop is satisfied as well. Thus, v is the
+    // inverse of m' = y * R^(-1) modulo n, i.e. y^(-1) * R mod n, and
+    // 0 < v < n
+    binary_loop(m, n, r2).1
+}
+
+fn main() {
+    let mut state = 2023;
+    for (name, n) in [("BN254 q", BN254_Q), ("BN254 r", BN254_R), ("secp256k1 p", SECP256K1_P)] {
+        let r2 = pow2_mod(512, &n);
+        // R mod n is the Montgomery form of 1, and R^(-1) mod n converts back
+        let r1 = pow2_mod(256, &n);
+        let r_inv = mod_inv(&r1, &n).unwrap();
+        let to_mont = |y: &U256| mul_mod(y, &r1, &n);
+        let from_mont = |m: &U256| mul_mod(m, &r_inv, &n);
+        assert!(mont_inv(&r1, &n, &r2) == r1, "Incorrect inverse of one modulo {}!", name);
+        for _ in 0..100 {
+            let y = random_below(&n, &mut state);
+            let i = mont_inv(&to_mont(&y), &n, &r2);
+            assert!(!ge(&i, &n), "The inverse is not reduced modulo {}!", name);
+            assert!(mul_mod(&y, &from_mont(&i), &n) == ONE, "Incorrect inverse modulo {}!", name);
+            assert!(i == to_mont(&mod_inv(&y, &n).unwrap()), "Mismatch with mod_inv modulo {}!", name);
+        }
+        println!("{}: OK", name);
+    }
+}
@@ -0,0 +1,93 @@
+/*
+  The Multi-Limb Modular Inversion by Means of the Binary Extended Euclidean
+                  Algorithm: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// A 256-bit unsigned integer stored as four 64-bit limbs, the least
+// significant limb first. This is enough for the BN254 base and scalar field
+// moduli (254 bits) as well as for any other modulus below 2^256
+type U256 = [u64; 4];
+// Six limbs are enough for the BLS12-381 base field modulus (381 bits)
+type U384 = [u64; 6];
+
+const ZERO: U256 = [0, 0, 0, 0];
+const ONE: U256 = [1, 0, 0, 0];
+
+// The BN254 base field modulus q and scalar field modulus r
+const BN254_Q: U256 = [
+    0x3c208c16d87cfd47, 0x97816a916871ca8d, 0xb85045b68181585d, 0x30644e72e131a029,
+];
+const BN254_R: U256 = [
+    0x43e1f593f0000001, 0x2833e84879b97091, 0xb85045b68181585d, 0x30644e72e131a029,
+];
+// The secp256k1 base field modulus 2^256 - 2^32 - 977, which uses all 256 bits
+const SECP256K1_P: U256 = [
+    0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff,
+];
+// The BLS12-381 base field modulus p
+const BLS12_381_P: U384 = [
+    0xb9feffffffffaaab, 0x1eabfffeb153ffff, 0x6730d2a0f6b0f624,
+    0x64774b84f38512bf, 0x4b1ba7b6434bacd7, 0x1a0111ea397fe69a,
+];
+
+// The implementation for any number of limbs together with its proof is in
+// "MultiLimb.rs", which the other files working with multi-limb moduli
+// include as well; this file tests it for 256-bit and 384-bit moduli
+#[allow(dead_code)]
+mod limbs {
+    include!("MultiLimb.rs");
+}
+
+use limbs::*;
+
+// Computes the inverse of x modulo a prime p as x^(p - 2) mod p due to
+// Fermat's little theorem; this serves as the reference for mod_inv
+fn fermat_inv<const N: usize>(x: &[u64; N], p: &[u64; N]) -> [u64; N] {
+    pow_mod(x, &sub(p, &small(2)).0, p)
+}
+
+fn main() {
+    let mut state = 2023;
+    // The errors of the checked entry point, and an x above the modulus
+    let n = [15, 0, 0, 0];
+    assert!(mod_inv(&[7, 0, 0, 0], &n) == Ok([13, 0, 0, 0]), "Incorrect inverse!");
+    assert!(mod_inv(&[22, 0, 0, 0], &n) == Ok([13, 0, 0, 0]), "Incorrect inverse!");
+    assert!(mod_inv(&[u64::MAX; 4], &n) == Err(InversionError::NotCoprime { gcd: n }), "Incorrect error!");
+    assert!(mod_inv(&[u64::MAX - 1; 4], &n) == Ok([11, 0, 0, 0]), "Incorrect inverse!");
+    assert!(mod_inv(&[6, 0, 0, 0], &n) == Err(InversionError::NotCoprime { gcd: [3, 0, 0, 0] }), "Incorrect error!");
+    assert!(mod_inv(&ZERO, &n) == Err(InversionError::NonPositive), "Incorrect error!");
+    assert!(mod_inv(&ONE, &[16, 0, 0, 0]) == Err(InversionError::EvenModulus), "Incorrect error!");
+    assert!(mod_inv(&ONE, &ONE) == Err(InversionError::ModulusTooSmall), "Incorrect error!");
+    for (name, p) in [("BN254 q", BN254_Q), ("BN254 r", BN254_R), ("secp256k1 p", SECP256K1_P)] {
+        let minus_one = sub(&p, &ONE).0;
+        let half = shr1(&add(&p, &ONE).0, false);
+        // Edge cases: 1 and -1 are self-inverse, the inverse of 2 is (p + 1) / 2
+        assert!(mod_inv_unchecked(&ONE, &p) == ONE, "Incorrect inverse of 1 modulo {}!", name);
+        assert!(mod_inv_unchecked(&minus_one, &p) == minus_one, "Incorrect inverse of -1 modulo {}!", name);
+        assert!(mod_inv_unchecked(&[2, 0, 0, 0], &p) == half, "Incorrect inverse of 2 modulo {}!", name);
+        for _ in 0..100 {
+            let x = random_below(&p, &mut state);
+            let i = mod_inv_unchecked(&x, &p);
+            assert!(!ge(&i, &p), "The inverse is not reduced modulo {}!", name);
+            assert!(mul_mod(&x, &i, &p) == ONE, "Incorrect inverse modulo {}!", name);
+            assert!(i == fermat_inv(&x, &p), "Mismatch with Fermat modulo {}!", name);
+        }
+        // The checked entry point: x >= n is reduced by the loop itself
+        let x = random_below(&p, &mut state);
+        let (x_plus_p, carry) = add(&x, &p);
+        if !carry { assert!(mod_inv(&x_plus_p, &p) == Ok(mod_inv_unchecked(&x, &p)), "Incorrect inverse modulo {}!", name); }
+        assert!(mod_inv(&p, &p) == Err(InversionError::NotCoprime { gcd: p }), "Incorrect error modulo {}!", name);
+        assert!(mod_inv(&ZERO, &p) == Err(InversionError::NonPositive), "Incorrect error modulo {}!", name);
+        println!("{}: OK", name);
+    }
+    // The same code for six limbs
+    let p = BLS12_381_P;
+    for _ in 0..20 {
+        let x = random_below(&p, &mut state);
+        let i = mod_inv(&x, &p).unwrap();
+        assert!(mul_mod(&x, &i, &p) == small(1) && i == fermat_inv(&x, &p), "Incorrect inverse modulo BLS12-381 p!");
+    }
+    assert!(mod_inv(&[u64::MAX; 6], &p) == mod_inv(&sub(&[u64::MAX; 6], &p).0, &p), "Incorrect inverse modulo BLS12-381 p!");
+    println!("BLS12-381 p: OK");
+}
@@ -0,0 +1,171 @@
+/*
+   The Inversion in F_p[X] / (f) by Means of the Extended Euclidean Algorithm
+                  for Polynomials: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// The classic mod_inv relies only on the division with remainder, and the
+// polynomials over a field F_p have it as well: the degree plays the role of
+// the absolute value. Thus, the same loop inverts g modulo f in F_p[X], which
+// is the inversion in the extension field F_p^k = F_p[X] / (f) for an
+// irreducible f of degree k. Unlike for integers, the GCD is defined up to a
+// non-zero constant factor, so the Bezout identity gives c * 1 for some
+// constant c, which has to be divided out
+
+// A polynomial over F_p, the coefficient of X^i at index i, without leading
+// zero coefficients (0 is the empty vector)
+type Poly = Vec<u64>;
+
+// The reasons why mod_inv cannot compute the inverse of g modulo f: only
+// ModulusTooSmall, if deg f < 1, and NotCoprime, if GCD(g, f) = gcd is not
+// constant; gcd is monic
+include!("InversionError.rs");
+
+// The classic mod_inv of "Proof and Implementation of Euclidean
+// Inversion.rs", which takes any modulus below 2^63
+#[allow(dead_code)]
+mod classic {
+    include!("Proof and Implementation of Euclidean Inversion.rs");
+
+    pub fn inv(x: i64, n: i64) -> i64 {
+        mod_inv_unchecked(x, n)
+    }
+}
+
+// Inverts a non-zero coefficient x < p modulo the prime p < 2^63, so both fit
+// in i64
+fn field_inv(x: u64, p: u64) -> u64 {
+    classic::inv(x as i64, p as i64) as u64
+}
+
+fn field_mul(a: u64, b: u64, p: u64) -> u64 {
+    (a as u128 * b as u128 % p as u128) as u64
+}
+
+fn norm(mut a: Poly) -> Poly {
+    while a.last() == Some(&0) { a.pop(); }
+    a
+}
+
+// Returns a - b
+fn sub(a: &Poly, b: &Poly, p: u64) -> Poly {
+    let coeff = |c: &Poly, i: usize| *c.get(i).unwrap_or(&0);
+    norm((0..a.len().max(b.len())).map(|i| (coeff(a, i) + p - coeff(b, i)) % p).collect())
+}
+
+fn mul(a: &Poly, b: &Poly, p: u64) -> Poly {
+    if a.is_empty() || b.is_empty() { return vec![]; }
+    let mut r = vec![0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() { r[i + j] = (r[i + j] + field_mul(x, y, p)) % p; }
+    }
+    norm(r)
+}
+
+// Returns c * a for a constant c
+fn scale(a: &Poly, c: u64, p: u64) -> Poly {
+    norm(a.iter().map(|&x| field_mul(x, c, p)).collect())
+}
+
+// Returns the quotient and the remainder of a / b for b != 0 by the long
+// division: each step cancels the leading coefficient of the remainder
+fn divrem(a: &Poly, b: &Poly, p: u64) -> (Poly, Poly) {
+    if a.len() < b.len() { return (vec![], a.clone()); }
+    let (mut r, lead_inv) = (a.clone(), field_inv(*b.last().unwrap(), p));
+    let mut q = vec![0; a.len() - b.len() + 1];
+    for i in (0..q.len()).rev() {
+        let c = field_mul(r[i + b.len() - 1], lead_inv, p);
+        q[i] = c;
+        for (j, &y) in b.iter().enumerate() { r[i + j] = (r[i + j] + p - field_mul(c, y, p)) % p; }
+    }
+    r.truncate(b.len() - 1);
+    (q, norm(r))
+}
+
+// Computes the multiplicative inverse of g modulo f in F_p[X] by applying the
+// Extended Euclidean Algorithm for a prime p < 2^63. If deg f < 1, or g and f
+// are not coprime, the reason why the inverse does not exist is returned
+// instead. The primality of p is not checked
+fn mod_inv(g: &Poly, f: &Poly, p: u64) -> Result<Poly, InversionError<Poly>> {
+    if f.len() < 2 { return Err(InversionError::ModulusTooSmall); }
+    // Working not with g, but with such g' that deg g' < deg f and g = g'
+    // (mod f)
+    let (mut s, mut x_s, mut b, mut x_b) = (divrem(g, f, p).1, vec![1], f.clone(), vec![]);
+    // Now s = g', b = f. As in the classic mod_inv, we have
+    // (1) s = x_s * g' + n_s * f; (2) b = x_b * g' + n_b * f;
+    // and each iteration until s = 0 uses "GCD(s, b) = GCD(b mod s, s)".
+    // Also (3) deg x_s = deg f - deg b and deg x_b < deg x_s: it holds now,
+    // since deg x_s = 0 = deg f - deg b and x_b = 0. In each iteration
+    // deg q = deg b - deg s > 0, so deg (q * x_s) = deg f - deg s, which
+    // exceeds deg x_b; thus, the new x_s = x_b - q * x_s has degree
+    // deg f - deg s, where s is the new b, and (3) is preserved
+    while !s.is_empty() {
+        let (q, r) = divrem(&b, &s, p);
+        let x_next = sub(&x_b, &mul(&q, &x_s, p), p);
+        (s, x_s, b, x_b) = (r, x_next, s, x_s);
+    }
+    // Now we have b = GCD(0, b) = GCD(g', f) up to a constant factor. If
+    // deg b > 0, then g' is not invertible modulo f. If b is a constant c,
+    // then c = x_b * g' + n_b * f, so (x_b / c) * g' = 1 (mod f). Due to (3),
+    // deg x_b < deg x_s = deg f - deg b before the last iteration, so the
+    // result is reduced modulo f
+    let c_inv = field_inv(*b.last().unwrap(), p);
+    if b.len() > 1 { return Err(InversionError::NotCoprime { gcd: scale(&b, c_inv, p) }); }
+    Ok(scale(&x_b, c_inv, p))
+}
+
+// Computes g^e mod f by the square-and-multiply method
+fn pow_mod(g: &Poly, mut e: u64, f: &Poly, p: u64) -> Poly {
+    let (mut r, mut x) = (vec![1], divrem(g, f, p).1);
+    while e > 0 {
+        if e & 1 == 1 { r = divrem(&mul(&r, &x, p), f, p).1; }
+        (x, e) = (divrem(&mul(&x, &x, p), f, p).1, e >> 1);
+    }
+    r
+}
+
+include!("SplitMix64.rs");
+
+fn main() {
+    // F_7^3 = F_7[X] / (X^3 + 3), since -3 = 4 is not a cube modulo 7: every
+    // non-zero element is invertible, and the inverse is g^(7^3 - 2) due to
+    // Lagrange's theorem for the multiplicative group of order 7^3 - 1
+    let (p, f) = (7, vec![3, 0, 0, 1]);
+    for i in 1..343 {
+        let g = norm(vec![i % 7, i / 7 % 7, i / 49]);
+        let inv = mod_inv(&g, &f, p).unwrap();
+        assert!(inv.len() < f.len(), "The inverse is not reduced!");
+        assert!(divrem(&mul(&g, &inv, p), &f, p).1 == [1], "Incorrect inverse!");
+        assert!(inv == pow_mod(&g, 341, &f, p), "Mismatch with the exponentiation!");
+    }
+    // F_p^2 = F_p[X] / (X^2 + 1) for p = 3 (mod 4), like the quadratic
+    // extension of BN254, and F_p^12 for a random modulus of degree 12
+    let mut state = 2023;
+    for p in [(1 << 61) - 1, (1 << 62) - 57, (1 << 63) - 25] {
+        let mut f12: Poly = (0..12).map(|_| next_u64(&mut state) % p).collect();
+        f12.push(1);
+        for f in [vec![1, 0, 1], f12] {
+            for _ in 0..1000 {
+                let g: Poly = norm((1..f.len()).map(|_| next_u64(&mut state) % p).collect());
+                match mod_inv(&g, &f, p) {
+                    Ok(inv) => assert!(divrem(&mul(&g, &inv, p), &f, p).1 == [1], "Incorrect inverse!"),
+                    Err(InversionError::NotCoprime { gcd }) => {
+                        assert!(divrem(&f, &gcd, p).1.is_empty() && divrem(&g, &gcd, p).1.is_empty(), "Incorrect GCD!");
+                    }
+                    Err(e) => panic!("Unexpected error {:?}!", e),
+                }
+            }
+        }
+    }
+    // The coefficients above 2^62, where x + p overflows i64
+    let p = (1 << 63) - 25;
+    assert!(field_inv(p - 1, p) == p - 1 && field_inv(2, p) == p / 2 + 1, "Incorrect inverse!");
+    assert!(mod_inv(&vec![p - 1, p - 1], &vec![1, 0, 1], p) == Ok(vec![p / 2, p / 2 + 1]), "Incorrect inverse!");
+    // A reducible modulus: X^2 - 1 = (X - 1) * (X + 1) over F_7
+    let (p, f) = (7, vec![6, 0, 1]);
+    assert!(mod_inv(&vec![5, 2], &f, p) == Err(InversionError::NotCoprime { gcd: vec![6, 1] }), "Incorrect error!");
+    assert!(mod_inv(&vec![], &f, p) == Err(InversionError::NotCoprime { gcd: f.clone() }), "Incorrect error!");
+    assert!(mod_inv(&vec![2, 1], &f, p) == Ok(vec![3, 2]), "Incorrect inverse!");
+    assert!(mod_inv(&vec![2, 1], &vec![3], p) == Err(InversionError::ModulusTooSmall), "Incorrect error!");
+    println!("{:?}", mod_inv(&vec![0, 1], &vec![3, 0, 0, 1], 7));
+}
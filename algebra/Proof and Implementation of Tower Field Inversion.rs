@@ -0,0 +1,204 @@
+/*
+  The Inversion in the BN254 Extension Tower Fp2 / Fp6 / Fp12 by Means of the
+                  Norm Maps: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// The pairing of BN254 takes values in Fp12, which is built as a tower:
+// Fp2 = Fp[u] / (u^2 + 1), Fp6 = Fp2[v] / (v^3 - xi) with xi = 9 + u and
+// Fp12 = Fp6[w] / (w^2 - v). The inversion in each level is reduced to the
+// inversion in the level below by multiplying by the conjugates, whose
+// product with the element is its norm, an element of the smaller field.
+// Thus, an inversion in Fp12 costs a single mod_inv in Fp and a few dozen
+// multiplications
+
+// A 256-bit unsigned integer stored as four 64-bit limbs, the least
+// significant limb first
+type U256 = [u64; 4];
+
+const ZERO: U256 = [0, 0, 0, 0];
+const ONE: U256 = [1, 0, 0, 0];
+
+// The BN254 base field modulus q
+const Q: U256 = [0x3c208c16d87cfd47, 0x97816a916871ca8d, 0xb85045b68181585d, 0x30644e72e131a029];
+
+// The elements of the tower; a + b * u is [a, b], c_0 + c_1 * v + c_2 * v^2
+// is [c_0, c_1, c_2], and a + b * w is [a, b]
+type Fp = U256;
+type Fp2 = [Fp; 2];
+type Fp6 = [Fp2; 3];
+type Fp12 = [Fp6; 2];
+
+// The arithmetic and the binary mod_inv of "Proof and Implementation of
+// Multi-Limb Binary Euclidean Inversion.rs" for four limbs
+#[allow(dead_code)]
+mod limbs {
+    include!("MultiLimb.rs");
+}
+
+use limbs::*;
+
+fn fp_add(a: &Fp, b: &Fp) -> Fp {
+    add_mod(a, b, &Q)
+}
+
+fn fp_sub(a: &Fp, b: &Fp) -> Fp {
+    sub_mod(a, b, &Q)
+}
+
+fn fp_mul(a: &Fp, b: &Fp) -> Fp {
+    mul_mod(a, b, &Q)
+}
+
+// The inverse of a non-zero element of Fp
+fn fp_inv(a: &Fp) -> Fp {
+    mod_inv(a, &Q).expect("Zero is not invertible in Fp!")
+}
+
+fn fp2_add(a: &Fp2, b: &Fp2) -> Fp2 {
+    [fp_add(&a[0], &b[0]), fp_add(&a[1], &b[1])]
+}
+
+fn fp2_sub(a: &Fp2, b: &Fp2) -> Fp2 {
+    [fp_sub(&a[0], &b[0]), fp_sub(&a[1], &b[1])]
+}
+
+// (a_0 + a_1 * u) * (b_0 + b_1 * u) = (a_0 * b_0 - a_1 * b_1) +
+// (a_0 * b_1 + a_1 * b_0) * u, since u^2 = -1
+fn fp2_mul(a: &Fp2, b: &Fp2) -> Fp2 {
+    [
+        fp_sub(&fp_mul(&a[0], &b[0]), &fp_mul(&a[1], &b[1])),
+        fp_add(&fp_mul(&a[0], &b[1]), &fp_mul(&a[1], &b[0])),
+    ]
+}
+
+// (a_0 + a_1 * u) * (9 + u) = (9 * a_0 - a_1) + (a_0 + 9 * a_1) * u
+fn fp2_mul_by_xi(a: &Fp2) -> Fp2 {
+    let nine = |x: &Fp| fp_mul(x, &[9, 0, 0, 0]);
+    [fp_sub(&nine(&a[0]), &a[1]), fp_add(&a[0], &nine(&a[1]))]
+}
+
+// The inverse of a non-zero element of Fp2
+fn fp2_inv(a: &Fp2) -> Fp2 {
+    // The conjugate of a = a_0 + a_1 * u is a_0 - a_1 * u, and their product
+    // is the norm a_0^2 + a_1^2, which lies in Fp. It is not 0 for a != 0,
+    // since -1 is not a square modulo q = 3 (mod 4), i.e. u^2 + 1 is
+    // irreducible. Thus, a^(-1) = (a_0 - a_1 * u) / (a_0^2 + a_1^2)
+    let norm_inv = fp_inv(&fp_add(&fp_mul(&a[0], &a[0]), &fp_mul(&a[1], &a[1])));
+    [fp_mul(&a[0], &norm_inv), fp_mul(&fp_sub(&ZERO, &a[1]), &norm_inv)]
+}
+
+fn fp6_add(a: &Fp6, b: &Fp6) -> Fp6 {
+    [fp2_add(&a[0], &b[0]), fp2_add(&a[1], &b[1]), fp2_add(&a[2], &b[2])]
+}
+
+fn fp6_sub(a: &Fp6, b: &Fp6) -> Fp6 {
+    [fp2_sub(&a[0], &b[0]), fp2_sub(&a[1], &b[1]), fp2_sub(&a[2], &b[2])]
+}
+
+// The schoolbook product, where v^3 = xi and v^4 = xi * v
+fn fp6_mul(a: &Fp6, b: &Fp6) -> Fp6 {
+    let m = |i: usize, j: usize| fp2_mul(&a[i], &b[j]);
+    [
+        fp2_add(&m(0, 0), &fp2_mul_by_xi(&fp2_add(&m(1, 2), &m(2, 1)))),
+        fp2_add(&fp2_add(&m(0, 1), &m(1, 0)), &fp2_mul_by_xi(&m(2, 2))),
+        fp2_add(&fp2_add(&m(0, 2), &m(1, 1)), &m(2, 0)),
+    ]
+}
+
+// (c_0 + c_1 * v + c_2 * v^2) * v = xi * c_2 + c_0 * v + c_1 * v^2
+fn fp6_mul_by_v(a: &Fp6) -> Fp6 {
+    [fp2_mul_by_xi(&a[2]), a[0], a[1]]
+}
+
+// The inverse of a non-zero element of Fp6
+fn fp6_inv(a: &Fp6) -> Fp6 {
+    // For c = c_0 + c_1 * v + c_2 * v^2 let
+    // t_0 = c_0^2 - xi * c_1 * c_2, t_1 = xi * c_2^2 - c_0 * c_1 and
+    // t_2 = c_1^2 - c_0 * c_2. Then c * (t_0 + t_1 * v + t_2 * v^2) has the
+    // coefficients c_0 * t_0 + xi * (c_1 * t_2 + c_2 * t_1) at 1,
+    // c_0 * t_1 + c_1 * t_0 + xi * c_2 * t_2 at v and
+    // c_0 * t_2 + c_1 * t_1 + c_2 * t_0 at v^2. Expanding the last two gives
+    // 0: e.g. c_0 * c_1^2 - c_0^2 * c_2 + xi * c_1 * c_2^2 - c_0 * c_1^2 +
+    // c_0^2 * c_2 - xi * c_1 * c_2^2. Thus, the product is the norm
+    // N = c_0 * t_0 + xi * (c_1 * t_2 + c_2 * t_1), which lies in Fp2. If
+    // N = 0, then t_0 = t_1 = t_2 = 0, since c != 0 is invertible in the field
+    // Fp6 (v^3 - xi is irreducible, since xi is not a cube in Fp2). Then
+    // c_1^2 = c_0 * c_2 and c_0^2 = xi * c_1 * c_2. If c_0 = 0, this gives
+    // c_1 = 0 and then xi * c_2^2 = 0, i.e. c = 0. Otherwise, c_2 =
+    // c_1^2 / c_0 and c_0^3 = xi * c_1^3, so xi = (c_0 / c_1)^3 is a cube.
+    // Thus, N != 0 and c^(-1) = (t_0 + t_1 * v + t_2 * v^2) / N
+    let (c0, c1, c2) = (&a[0], &a[1], &a[2]);
+    let t0 = fp2_sub(&fp2_mul(c0, c0), &fp2_mul_by_xi(&fp2_mul(c1, c2)));
+    let t1 = fp2_sub(&fp2_mul_by_xi(&fp2_mul(c2, c2)), &fp2_mul(c0, c1));
+    let t2 = fp2_sub(&fp2_mul(c1, c1), &fp2_mul(c0, c2));
+    let norm = fp2_add(&fp2_mul(c0, &t0), &fp2_mul_by_xi(&fp2_add(&fp2_mul(c1, &t2), &fp2_mul(c2, &t1))));
+    let norm_inv = fp2_inv(&norm);
+    [fp2_mul(&t0, &norm_inv), fp2_mul(&t1, &norm_inv), fp2_mul(&t2, &norm_inv)]
+}
+
+// (a_0 + a_1 * w) * (b_0 + b_1 * w) = (a_0 * b_0 + a_1 * b_1 * v) +
+// (a_0 * b_1 + a_1 * b_0) * w, since w^2 = v
+fn fp12_mul(a: &Fp12, b: &Fp12) -> Fp12 {
+    [
+        fp6_add(&fp6_mul(&a[0], &b[0]), &fp6_mul_by_v(&fp6_mul(&a[1], &b[1]))),
+        fp6_add(&fp6_mul(&a[0], &b[1]), &fp6_mul(&a[1], &b[0])),
+    ]
+}
+
+// The inverse of a non-zero element of Fp12
+fn fp12_inv(a: &Fp12) -> Fp12 {
+    // As in Fp2, the conjugate of a = a_0 + a_1 * w is a_0 - a_1 * w, and
+    // their product is the norm a_0^2 - a_1^2 * v, which lies in Fp6. It is
+    // not 0 for a != 0, since v is not a square in Fp6, i.e. w^2 - v is
+    // irreducible. Thus, a^(-1) = (a_0 - a_1 * w) / (a_0^2 - a_1^2 * v)
+    let norm = fp6_sub(&fp6_mul(&a[0], &a[0]), &fp6_mul_by_v(&fp6_mul(&a[1], &a[1])));
+    let norm_inv = fp6_inv(&norm);
+    [fp6_mul(&a[0], &norm_inv), fp6_sub(&[[ZERO; 2]; 3], &fp6_mul(&a[1], &norm_inv))]
+}
+
+// Returns a pseudo-random non-zero element of Fp, so the elements built from
+// it are non-zero in the tests
+fn random_fp(state: &mut u64) -> Fp {
+    random_below(&Q, state)
+}
+
+fn random_fp2(state: &mut u64) -> Fp2 {
+    [random_fp(state), random_fp(state)]
+}
+
+fn random_fp6(state: &mut u64) -> Fp6 {
+    [random_fp2(state), random_fp2(state), random_fp2(state)]
+}
+
+fn main() {
+    let mut state = 2023;
+    let (one2, zero2) = ([ONE, ZERO], [ZERO, ZERO]);
+    let one6 = [one2, zero2, zero2];
+    let one12 = [one6, [zero2; 3]];
+    // u^2 = -1, v^3 = xi, w^2 = v
+    let (u, v) = ([ZERO, ONE], [zero2, one2, zero2]);
+    assert!(fp2_mul(&u, &u) == [fp_sub(&ZERO, &ONE), ZERO], "Incorrect Fp2!");
+    assert!(fp6_mul(&fp6_mul(&v, &v), &v) == [fp2_mul_by_xi(&one2), zero2, zero2], "Incorrect Fp6!");
+    let w = [[zero2; 3], one6];
+    assert!(fp12_mul(&w, &w) == [v, [zero2; 3]], "Incorrect Fp12!");
+    assert!(fp12_inv(&one12) == one12, "Incorrect inverse of 1!");
+    for _ in 0..20 {
+        let a = random_fp2(&mut state);
+        assert!(fp2_mul(&a, &fp2_inv(&a)) == one2, "Incorrect inverse in Fp2!");
+        let b = random_fp6(&mut state);
+        assert!(fp6_mul(&b, &fp6_inv(&b)) == one6, "Incorrect inverse in Fp6!");
+        // Sparse elements, such as those in the Miller loop, as well
+        let sparse = [random_fp2(&mut state), zero2, zero2];
+        assert!(fp6_mul(&sparse, &fp6_inv(&sparse)) == one6, "Incorrect inverse in Fp6!");
+        let c = [random_fp6(&mut state), random_fp6(&mut state)];
+        let c_inv = fp12_inv(&c);
+        assert!(fp12_mul(&c, &c_inv) == one12, "Incorrect inverse in Fp12!");
+        let d = [[zero2; 3], random_fp6(&mut state)];
+        assert!(fp12_mul(&d, &fp12_inv(&d)) == one12, "Incorrect inverse in Fp12!");
+        // (c * d)^(-1) = c^(-1) * d^(-1)
+        let cd_inv = fp12_inv(&fp12_mul(&c, &d));
+        assert!(cd_inv == fp12_mul(&c_inv, &fp12_inv(&d)), "Incorrect inverse of a product!");
+    }
+    println!("OK");
+}
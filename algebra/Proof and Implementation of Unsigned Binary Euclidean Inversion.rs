@@ -0,0 +1,67 @@
+/*
+  The Modular Inversion for the Full u64 Range by Means of the Binary Extended
+             Euclidean Algorithm: Implementation in Rust and Proof
+
+                               October 2026
+*/
+// The binary mod_inv works on i64 and computes u - v + n and u + n, so it
+// overflows once n approaches 2^63 and cannot take moduli above 2^63 - 1 at
+// all, e.g. the Goldilocks prime 2^64 - 2^32 + 1. The variant below works on
+// u64 and never computes a value outside [0, 2^64), so any odd modulus below
+// 2^64 is supported
+const GOLDILOCKS: u64 = 0xffff_ffff_0000_0001;
+
+// The loop of the algorithm with its proof is generic over the unsigned
+// primitive types, so that "Proof and Implementation of Generic Modular
+// Inversion.rs" shares it; here it is used for u64
+include!("Word.rs");
+
+// Computes the multiplicative inverse of x modulo n by applying the binary
+// Extended Euclidean Algorithm. For applying this method n must be odd,
+// x and n must be coprime (because if they are not comprime, the inverse
+// does not exist), both x and n must be positive
+fn mod_inv(x: u64, n: u64) -> u64 {
+    binary_loop(x, n).1
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b > 0 { (a, b) = (b, a % b); }
+    a
+}
+
+// Checks the inverse of x modulo n by multiplying with u128 intermediates
+fn check(x: u64, n: u64) {
+    let i = mod_inv(x, n);
+    assert!(0 < i && i < n, "The inverse of {} mod {} is not reduced!", x, n);
+    assert!((i as u128 * x as u128) % n as u128 == 1, "Incorrect inverse of {} mod {}!", x, n);
+}
+
+include!("SplitMix64.rs");
+
+fn main() {
+    // Every odd modulus in [2^64 - 2^10, 2^64) and in [2^63 - 2^9, 2^63 + 2^9)
+    // with every coprime x near 0, near n / 2 and near n; such values
+    // maximize u + n and u - v + n in the signed version
+    let moduli = (u64::MAX - (1 << 10) + 1..=u64::MAX).chain((1 << 63) - (1 << 9)..(1 << 63) + (1 << 9));
+    for n in moduli.filter(|n| n & 1 > 0) {
+        let xs = (1..64).chain(n / 2 - 32..n / 2 + 32).chain(n - 63..n);
+        for x in xs.filter(|&x| gcd(x, n) == 1) { check(x, n); }
+    }
+    // Random inputs for the Goldilocks prime, the largest prime below 2^64
+    // and the composite 2^64 - 1 = 3 * 5 * 17 * 257 * 641 * 65537 * 6700417
+    let mut state = 2023;
+    for n in [GOLDILOCKS, u64::MAX - 58, u64::MAX] {
+        for _ in 0..100_000 {
+            let x = next_u64(&mut state) % n;
+            if x > 0 && gcd(x, n) == 1 { check(x, n); }
+        }
+    }
+    assert!(mod_inv(2, GOLDILOCKS) == GOLDILOCKS / 2 + 1, "Incorrect inverse!");
+    assert!(mod_inv(GOLDILOCKS - 1, GOLDILOCKS) == GOLDILOCKS - 1, "Incorrect inverse!");
+    // For the pairs that are not coprime the loop ends with b = GCD(x, n)
+    for (x, n) in [(0, 15), (6, 15), (10, 15), (3 << 40, u64::MAX), (u64::MAX - 6700417, u64::MAX)] {
+        assert!(binary_loop(x, n).0 == gcd(x, n), "Incorrect GCD of {} and {}!", x, n);
+    }
+    let (x, n) = (13, GOLDILOCKS);
+    println!("{}", mod_inv(x, n));
+}
@@ -0,0 +1,10 @@
+// A simple SplitMix64 generator, which is enough to produce test inputs. It
+// is shared by all the files that need random inputs for their tests, which
+// include it by include!("SplitMix64.rs")
+fn next_u64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
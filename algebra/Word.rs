@@ -0,0 +1,86 @@
+// The unsigned primitive integer types behind the Word trait and the loop of
+// the binary Extended Euclidean Algorithm of "Proof and Implementation of
+// Unsigned Binary Euclidean Inversion.rs" with its proof, which is generic
+// over them. The files working with such integers include this one by
+// include!("Word.rs")
+use std::ops::{Add, Div, Mul, Rem, Shr, Sub};
+
+// The operations the inversion algorithms need from an unsigned integer. The
+// primitive types implement it below; a big integer type gets the algorithms
+// by implementing Word as well
+trait Word:
+    Copy
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Shr<u32, Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    fn is_odd(self) -> bool;
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {$(
+        impl Word for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            fn is_odd(self) -> bool { self & 1 == 1 }
+        }
+    )*};
+}
+
+impl_word!(u8, u16, u32, u64, u128, usize);
+
+// Runs the loop of the binary Extended Euclidean Algorithm for x and n and
+// returns the final b and v. n must be odd and 0 <= x < n; x and n need not
+// be coprime
+fn binary_loop<T: Word>(x: T, n: T) -> (T, T) {
+    let (mut a, mut b, mut u, mut v) = (x, n, T::ONE, T::ZERO);
+    // Now a = x, b = n;
+    // (1) b is odd; (2) u < n and v < n; (3) a = u * x (mod n);
+    // (4) b = v * x (mod n); (5) GCD(a, b) = GCD(x, n);
+    // (6) a, b, u and v are less than 2^w, where w is the width of T.
+    // Non-negativity is guaranteed by the type, so (6) of the signed version
+    // is replaced with the absence of overflows. a and b never grow, so they
+    // stay below 2^w, and (2) together with n < 2^w bounds u and v. In each
+    // iteration we perform the transformation of a and b as well as their
+    // accompanying coefficients u and v, which preserves (1)-(6) and
+    // decreases a + b. Thus, when a is 0, b = GCD(x, n) due to (5), and if it
+    // is 1, then v is the inverse of x modulo n due to (4). Also, 0 <= v < n
+    // due to (2)
+    while a > T::ZERO {
+        if a.is_odd() {
+            // Both a and b are odd here. We decrease the greatest by the
+            // smallest and satisfy (5), because GCD(p, q) = GCD(p - q, q),
+            // update the greatest's accompanying coefficient to satisfy
+            // (3) and (4), swap the values for a and b as well as for their
+            // accompanying coefficients, if this is required, to satisfy (1)
+            // without breaking (3)-(5). The differences a - b and b - a are
+            // computed only when they are non-negative
+            if a < b { (a, b, u, v) = (b, a, v, u); }
+            a = a - b;
+            // The coefficient must become u - v mod n. The signed version
+            // computes u - v and then adds n if it is negative, which needs
+            // one more bit. Instead, if u < v we compute u + (n - v), where
+            // 0 < n - v <= n due to (2), and the sum is u - v + n < n, so
+            // neither step overflows and (2) is satisfied
+            u = if u >= v { u - v } else { u + (n - v) };
+        }
+        // Here a is even and (1)-(6) are satisfied. We divide a by 2 and still
+        // satisfy (5), since b is odd due to (1) and GCD(p, q) = GCD(p / 2, q)
+        // for even p and odd q. As the result, only (3) is not satisfied
+        a = a >> 1;
+        // In order to satisfy (3) without breaking (1)-(2) and (4)-(6),
+        // u should be set to u * 2^(-1) mod n. If u is even, it is done by
+        // dividing u by 2. For odd u the signed version sets u to (u + n) / 2,
+        // but u + n may reach 2^(w + 1) - 4 here. Since both u and n are odd,
+        // (u + n) / 2 = (u - 1) / 2 + (n - 1) / 2 + 1 = (u >> 1) + (n >> 1) + 1,
+        // which is less than n due to (2) and is computed without overflow
+        u = if u.is_odd() { (u >> 1) + (n >> 1) + T::ONE } else { u >> 1 };
+    }
+    (b, v)
+}